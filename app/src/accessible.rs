@@ -0,0 +1,353 @@
+//! A terminal front end for screen readers.
+//!
+//! There is no grid art. After each command the selected cell and its neighbors are announced in a sentence,
+//! and rows, regions and counts can be read out on request.
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    io::{stdout, Write},
+};
+
+use crate::{
+    command_line::{self, SYNTAX_HELP},
+    input::{get_input, quit_terminal},
+};
+use minesweeper::{
+    board::Board,
+    cell::{Cell, Outcome},
+    frontend::{self, CommandSource, Renderer},
+    game::{Action, Command, Game},
+    label::{
+        cell_label, column_label, parse_cell_label, parse_column_label, parse_row_label, row_label,
+    },
+};
+
+/// The commands only this front end has, shown by `help`
+const QUERY_HELP: &str = "Move with up, down, left and right, or go to a cell with go C7.\n\
+r, f, u or c on their own act on the selected cell.\n\
+Read with: where, row 7, column C, region A1 D4, count, help.\n\
+Rows and columns count from 1, so row 7 and r 7 3 both take in C7.";
+
+/// Plays `game` with announcements instead of a grid until the player quits
+pub fn run<const W: usize, const H: usize>(game: Game<W, H>) -> Result<(), std::io::Error> {
+    return frontend::run(game, &mut Announcer, &mut Listener::default());
+}
+
+/// Announces the selected cell and its neighbors
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Announcer;
+impl Renderer for Announcer {
+    type Error = std::io::Error;
+    fn render<const W: usize, const H: usize>(
+        &mut self,
+        game: &Game<W, H>,
+    ) -> Result<(), Self::Error> {
+        if game.state().is_won() {
+            writeln!(
+                stdout(),
+                "You cleared the board in {} seconds.",
+                game.elapsed().as_secs()
+            )?;
+            return Ok(());
+        }
+        if game.state().is_game_over() {
+            // a chord can reveal more than one mine
+            let detonated = labels_of(game.board(), Outcome::DetonatedMine);
+            let mines = match detonated.len() {
+                1 => "a mine",
+                _ => "mines",
+            };
+            writeln!(
+                stdout(),
+                "You revealed {} at {}. Game over after {} seconds.",
+                mines,
+                detonated.join(", "),
+                game.elapsed().as_secs()
+            )?;
+            let wrong_flags = labels_of(game.board(), Outcome::WrongFlag);
+            if !wrong_flags.is_empty() {
+                writeln!(
+                    stdout(),
+                    "Flags that weren't on mines: {}.",
+                    wrong_flags.join(", ")
+                )?;
+            }
+            return Ok(());
+        }
+        if let Some(hint) = game.hint() {
+            writeln!(stdout(), "Hint: {} is safe.", cell_label(hint))?;
+        }
+        writeln!(stdout(), "{}", announce(game.board(), game.cell_index()))?;
+        return Ok(());
+    }
+}
+
+/// Reads commands with [get_input]. Game commands use the [one-line syntax](crate::command_line),
+/// queries are answered straight away without making a [Command]
+#[derive(Debug, Default, Clone)]
+pub struct Listener {
+    /// commands from the last line that haven't been played yet
+    queued: VecDeque<Command>,
+}
+impl CommandSource for Listener {
+    type Error = std::io::Error;
+    fn next_command<const W: usize, const H: usize>(
+        &mut self,
+        game: &Game<W, H>,
+    ) -> Result<Option<Command>, Self::Error> {
+        if game.state().is_finished() {
+            self.queued.clear();
+            return Ok(Some(if quit_terminal()? {
+                Command::Quit
+            } else {
+                Command::NewGame
+            }));
+        }
+        if let Some(command) = self.queued.pop_front() {
+            return Ok(Some(command));
+        }
+        loop {
+            let line = get_input("Command: ")?;
+            let words: Vec<&str> = line.split_whitespace().collect();
+
+            if let Some(command) = selection_command(game, &words) {
+                match command {
+                    Ok(command) => return Ok(Some(command)),
+                    Err(message) => writeln!(stdout(), "{}", message)?,
+                }
+                continue;
+            }
+            if let Some(answer) = query(game, &words) {
+                match answer {
+                    Ok(answer) | Err(answer) => writeln!(stdout(), "{}", answer)?,
+                }
+                continue;
+            }
+            match command_line::parse::<W, H>(&line) {
+                Ok(commands) => {
+                    self.queued.extend(commands);
+                    if let Some(command) = self.queued.pop_front() {
+                        return Ok(Some(command));
+                    }
+                }
+                Err(parse_error) => writeln!(stdout(), "{}. Type help for commands.", parse_error)?,
+            }
+        }
+    }
+}
+
+/// Returns the [Command] for moving the selection, or for an action word on its own, which acts on the selected cell.
+/// Returns [None] if `words` aren't one of those
+fn selection_command<const W: usize, const H: usize>(
+    game: &Game<W, H>,
+    words: &[&str],
+) -> Option<Result<Command, String>> {
+    let (row_index, column_index) = game.cell_index();
+    let select = |cell_index| Command::Select { cell_index };
+
+    return Some(match words {
+        ["up"] => row_index
+            .checked_sub(1)
+            .map(|row_index| select((row_index, column_index)))
+            .ok_or(String::from("Already at the top row.")),
+        ["down"] => Some(row_index + 1)
+            .filter(|&row_index| row_index < H)
+            .map(|row_index| select((row_index, column_index)))
+            .ok_or(String::from("Already at the bottom row.")),
+        ["left"] => column_index
+            .checked_sub(1)
+            .map(|column_index| select((row_index, column_index)))
+            .ok_or(String::from("Already at the first column.")),
+        ["right"] => Some(column_index + 1)
+            .filter(|&column_index| column_index < W)
+            .map(|column_index| select((row_index, column_index)))
+            .ok_or(String::from("Already at the last column.")),
+        ["go" | "g", label] => parse_cell_label(label)
+            .filter(|&(row_index, column_index)| row_index < H && column_index < W)
+            .map(select)
+            .ok_or(format!("{} is not a cell on the board.", label)),
+        [word] => {
+            let action = word.parse::<Action>().ok()?;
+            Ok(Command::Act {
+                action,
+                cell_index: game.cell_index(),
+            })
+        }
+        _ => return None,
+    });
+}
+
+/// Answers the queries only this front end has. Returns [None] if `words` aren't a query
+fn query<const W: usize, const H: usize>(
+    game: &Game<W, H>,
+    words: &[&str],
+) -> Option<Result<String, String>> {
+    let board = game.board();
+
+    return Some(match words {
+        ["help"] => Ok(format!("{}\n{}", QUERY_HELP, SYNTAX_HELP)),
+        ["where"] => Ok(announce(board, game.cell_index())),
+        ["count"] => Ok(count(game)),
+        ["row", row] => parse_row_label(row)
+            .filter(|&row_index| row_index < H)
+            .map(|row_index| {
+                let cells = (0..W).map(|column_index| (row_index, column_index));
+                return format!(
+                    "Row {}: {}.",
+                    row_label(row_index),
+                    read_cells(board, cells, true)
+                );
+            })
+            .ok_or(format!(
+                "There is no row {}. Rows go from {} to {}.",
+                row,
+                row_label(0),
+                row_label(H - 1)
+            )),
+        ["column", column] => parse_column_label(column)
+            .filter(|&column_index| column_index < W)
+            .map(|column_index| {
+                let cells = (0..H).map(|row_index| (row_index, column_index));
+                return format!(
+                    "Column {}: {}.",
+                    column_label(column_index),
+                    read_cells(board, cells, false)
+                );
+            })
+            .ok_or(format!(
+                "There is no column {}. Columns go from A to {}.",
+                column,
+                column_label(W - 1)
+            )),
+        ["region", first, last] => {
+            let parse = |label: &str| {
+                return parse_cell_label(label)
+                    .filter(|&(row_index, column_index)| row_index < H && column_index < W)
+                    .ok_or(format!("{} is not a cell on the board.", label));
+            };
+            match (parse(first), parse(last)) {
+                (Ok(first), Ok(last)) => {
+                    let columns = first.1.min(last.1)..=first.1.max(last.1);
+                    let rows: Vec<String> = (first.0.min(last.0)..=first.0.max(last.0))
+                        .map(|row_index| {
+                            let cells = columns
+                                .clone()
+                                .map(|column_index| (row_index, column_index));
+                            return format!(
+                                "Row {}: {}.",
+                                row_label(row_index),
+                                read_cells(board, cells, true)
+                            );
+                        })
+                        .collect();
+                    Ok(rows.join("\n"))
+                }
+                (Err(message), _) | (_, Err(message)) => Err(message),
+            }
+        }
+        _ => return None,
+    });
+}
+
+/// Returns the labels of the cells whose [Outcome] is `outcome`, row by row
+fn labels_of<const W: usize, const H: usize>(board: &Board<W, H>, outcome: Outcome) -> Vec<String> {
+    return board
+        .indices()
+        .filter(|&index| board[index].outcome() == outcome)
+        .map(cell_label)
+        .collect();
+}
+
+/// Describes a [Cell] in words, like "hidden" or "shows 3"
+fn describe(cell: &Cell) -> String {
+    return match (cell.is_revealed(), cell.is_flagged(), cell.is_mine()) {
+        (false, true, _) => String::from("flagged"),
+        (false, false, _) => String::from("hidden"),
+        (true, _, true) => String::from("mine"),
+        (true, _, false) if cell.local_mines() == 0 => String::from("empty"),
+        (true, _, false) => format!("shows {}", cell.local_mines()),
+    };
+}
+
+/// Announces the cell at `index` and sums up its neighbors, like "C7, row 7 column C, hidden; neighbors: 2 flagged, 1 shows 3"
+pub fn announce<const W: usize, const H: usize>(
+    board: &Board<W, H>,
+    index: (usize, usize),
+) -> String {
+    // neighbors grouped by description, so the summary is in a stable order
+    let mut neighbors = BTreeMap::new();
+    for neighbor in board.neighbors(index) {
+        *neighbors.entry(describe(&board[neighbor])).or_insert(0) += 1;
+    }
+    let neighbors: Vec<String> = neighbors
+        .into_iter()
+        .map(|(description, count)| {
+            // "2 show 1" rather than "2 shows 1"
+            let description = match description.strip_prefix("shows") {
+                Some(number) if count > 1 => format!("show{}", number),
+                _ => description,
+            };
+            return format!("{} {}", count, description);
+        })
+        .collect();
+
+    return format!(
+        "{}, row {} column {}, {}; neighbors: {}",
+        cell_label(index),
+        row_label(index.0),
+        column_label(index.1),
+        describe(&board[index]),
+        neighbors.join(", ")
+    );
+}
+
+/// Reads out `cells` in order, joining runs of cells that look the same, like "A to F hidden, G shows 1"
+/// - Cells along a row are named by their column letter, cells down a column by their row number
+fn read_cells<const W: usize, const H: usize>(
+    board: &Board<W, H>,
+    cells: impl Iterator<Item = (usize, usize)>,
+    is_row: bool,
+) -> String {
+    // (first label, last label, description)
+    let mut runs: Vec<(String, String, String)> = Vec::new();
+    for index in cells {
+        let label = if is_row {
+            column_label(index.1)
+        } else {
+            row_label(index.0)
+        };
+        let description = describe(&board[index]);
+        match runs.last_mut() {
+            Some((_, last, run_description)) if *run_description == description => *last = label,
+            _ => runs.push((label.clone(), label, description)),
+        }
+    }
+
+    let runs: Vec<String> = runs
+        .into_iter()
+        .map(|(first, last, description)| {
+            return if first == last {
+                format!("{} {}", first, description)
+            } else {
+                format!("{} to {} {}", first, last, description)
+            };
+        })
+        .collect();
+    return runs.join(", ");
+}
+
+/// Sums up the whole game
+fn count<const W: usize, const H: usize>(game: &Game<W, H>) -> String {
+    let board = game.board();
+    let hidden = board
+        .indices()
+        .filter(|&index| !board[index].is_revealed() && !board[index].is_flagged())
+        .count();
+    return format!(
+        "{} mines remaining, {} flags, {} hidden cells, {} seconds.",
+        game.remaining_mines(),
+        board.flag_count(),
+        hidden,
+        game.elapsed().as_secs()
+    );
+}
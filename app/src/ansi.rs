@@ -0,0 +1,173 @@
+//! Colored text rendering of a [Board] with ANSI escape codes
+
+use std::{
+    fmt::Display,
+    io::{stdout, IsTerminal},
+};
+
+use crossterm::style::{Attribute, Color, ContentStyle};
+
+use minesweeper::{
+    board::Board,
+    cell::{Cell, Outcome},
+    layout::{Border, GridLayout},
+};
+
+/// How much styling to add to text output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Styling {
+    /// Colors and attributes
+    Color,
+    /// Attributes like dim and reverse, but no colors. Used by front ends that need escape codes, like a cursor, when `NO_COLOR` is set
+    Monochrome,
+    /// No escape codes at all. Used when [stdout] isn't a terminal or `NO_COLOR` is set
+    Plain,
+}
+impl Styling {
+    /// Picks the [Styling] for [stdout]
+    /// - [Styling::Plain] when it isn't a terminal, or when the `NO_COLOR` environment variable is set and not empty
+    /// - [Styling::Color] otherwise
+    pub fn detect() -> Self {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        return if no_color || !stdout().is_terminal() {
+            Styling::Plain
+        } else {
+            Styling::Color
+        };
+    }
+}
+
+/// The classic color of a revealed cell's number: 1 blue, 2 green, 3 red, 4 navy, 5 maroon, 6 teal...
+pub fn number_color(local_mines: usize) -> Color {
+    return match local_mines {
+        0 => Color::DarkGrey,
+        1 => Color::Blue,
+        2 => Color::Green,
+        3 => Color::Red,
+        4 => Color::DarkBlue,
+        5 => Color::DarkRed,
+        6 => Color::DarkCyan,
+        // classically black, which can't be seen on a dark terminal
+        7 => Color::Magenta,
+        _ => Color::Grey,
+    };
+}
+
+/// Displays a [Board] as a grid with styled cells
+#[derive(Debug, Clone)]
+pub struct ColoredBoard<'a, const W: usize, const H: usize> {
+    board: &'a Board<W, H>,
+    styling: Styling,
+    /// shown in reverse video
+    selected: Option<(usize, usize)>,
+    /// shown underlined
+    last_move: Option<(usize, usize)>,
+    /// show each cell's [Outcome] instead of what the player sees
+    is_finished: bool,
+    layout: GridLayout,
+}
+impl<'a, const W: usize, const H: usize> ColoredBoard<'a, W, H> {
+    pub fn new(board: &'a Board<W, H>, styling: Styling) -> Self {
+        return Self {
+            board,
+            styling,
+            selected: None,
+            last_move: None,
+            is_finished: false,
+            layout: board.layout(Border::Box),
+        };
+    }
+    /// Highlights the cell at `selected`, like a cursor
+    pub fn selected(mut self, selected: Option<(usize, usize)>) -> Self {
+        self.selected = selected;
+        return self;
+    }
+    /// Marks the cell at `last_move`
+    pub fn last_move(mut self, last_move: Option<(usize, usize)>) -> Self {
+        self.last_move = last_move;
+        return self;
+    }
+
+    /// Shows each cell's [Outcome] instead of what the player sees, for a board whose game is over
+    pub fn finished(mut self, is_finished: bool) -> Self {
+        self.is_finished = is_finished;
+        return self;
+    }
+
+    /// Lays the board out with `layout`, see [Board::layout]. Defaults to the whole board with a [Border::Box]
+    pub fn layout(mut self, layout: GridLayout) -> Self {
+        self.layout = layout;
+        return self;
+    }
+
+    /// Returns the style of a [Cell]
+    fn cell_style(&self, index: (usize, usize), cell: &Cell) -> ContentStyle {
+        let mut style = ContentStyle::new();
+
+        if self.styling == Styling::Color {
+            style.foreground_color = Some(
+                match (cell.is_revealed(), cell.is_flagged(), cell.is_mine()) {
+                    (false, true, _) => Color::Yellow,
+                    (false, false, _) => Color::Grey,
+                    (true, _, true) => Color::Red,
+                    (true, _, false) => number_color(cell.local_mines()),
+                },
+            );
+        }
+        if !cell.is_revealed() && !cell.is_flagged() {
+            style.attributes.set(Attribute::Dim);
+        }
+        if cell.is_revealed() && (cell.is_mine() || cell.local_mines() > 0) {
+            style.attributes.set(Attribute::Bold);
+        }
+        if Some(index) == self.last_move {
+            style.attributes.set(Attribute::Underlined);
+        }
+        if self.is_finished {
+            style = self.outcome_style(cell.outcome(), style);
+        }
+        if Some(index) == self.selected {
+            style.attributes.unset(Attribute::Dim);
+            style.attributes.set(Attribute::Reverse);
+        }
+
+        return style;
+    }
+
+    /// Restyles a cell by its [Outcome]. Mistakes and the detonated mine stand out, keeping the attributes in `style` that mark the cursor and last move
+    fn outcome_style(&self, outcome: Outcome, mut style: ContentStyle) -> ContentStyle {
+        style.attributes.unset(Attribute::Dim);
+        style.attributes.set(Attribute::Bold);
+        if self.styling == Styling::Color {
+            style.foreground_color = Some(match outcome {
+                Outcome::CorrectFlag => Color::Green,
+                Outcome::WrongFlag | Outcome::DetonatedMine => Color::Red,
+                Outcome::MissedMine => Color::Yellow,
+                Outcome::Safe { local_mines } => number_color(local_mines),
+            });
+        }
+        match outcome {
+            Outcome::DetonatedMine => style.attributes.set(Attribute::Reverse),
+            Outcome::Safe { local_mines: 0 } => style.attributes.set(Attribute::Dim),
+            _ => {}
+        }
+        return style;
+    }
+}
+impl<const W: usize, const H: usize> Display for ColoredBoard<'_, W, H> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let style_cell = |index: (usize, usize), text: String| {
+            if self.styling == Styling::Plain {
+                return text;
+            }
+            return self
+                .cell_style(index, &self.board[index])
+                .apply(text)
+                .to_string();
+        };
+        if self.is_finished {
+            return self.board.fmt_outcomes(f, &self.layout, style_cell);
+        }
+        return self.board.fmt_grid(f, &self.layout, style_cell);
+    }
+}
@@ -0,0 +1,212 @@
+use std::{fmt::Write, path::PathBuf};
+
+use minesweeper::game::Difficulty;
+
+/// The front end to play with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Gui,
+    Terminal,
+    Tui,
+    Accessible,
+}
+impl Default for Mode {
+    /// The gui when it is built, otherwise the terminal
+    fn default() -> Self {
+        return if cfg!(feature = "gui") {
+            Mode::Gui
+        } else {
+            Mode::Terminal
+        };
+    }
+}
+
+/// The options parsed from the command line arguments
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub mode: Mode,
+    pub difficulty: Difficulty,
+    pub seed: Option<u64>,
+    /// the config file to use instead of the one at [default_path](crate::config::default_path)
+    pub config: Option<PathBuf>,
+    /// `--help` was passed, nothing should be played
+    pub help: bool,
+}
+impl Options {
+    /// Parses the command line arguments, not including the program name
+    /// # Errors
+    /// - When an argument is unknown
+    /// - When an option is missing its value, or the value can't be parsed
+    /// - When a flag is given a value
+    pub fn parse(
+        arguments: impl IntoIterator<Item = String>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut options = Options::default();
+        let mut arguments = arguments.into_iter();
+
+        while let Some(argument) = arguments.next() {
+            // allow both `--option value` and `--option=value`
+            let (name, inline_value) = match argument.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                None => (argument, None),
+            };
+            let mut value = || {
+                return inline_value
+                    .clone()
+                    .or_else(|| arguments.next())
+                    .ok_or(format!("{} needs a value", name));
+            };
+
+            match name.as_str() {
+                // a flag with a value, like `--gui=yes`, is a mistake rather than a choice
+                "-h" | "--help" | "-g" | "--gui" | "-t" | "--terminal" | "-u" | "--tui" | "-a"
+                | "--accessible"
+                    if inline_value.is_some() =>
+                {
+                    return Err(format!("{} doesn't take a value", name).into())
+                }
+                "-h" | "--help" => options.help = true,
+                "-g" | "--gui" => options.mode = Mode::Gui,
+                "-t" | "--terminal" => options.mode = Mode::Terminal,
+                "-u" | "--tui" => options.mode = Mode::Tui,
+                "-a" | "--accessible" => options.mode = Mode::Accessible,
+                "-d" | "--difficulty" => options.difficulty = value()?.parse()?,
+                "-s" | "--seed" => {
+                    let seed = value()?;
+                    let seed = seed
+                        .parse()
+                        .map_err(|_| format!("{} is not a valid seed", seed))?;
+                    options.seed = Some(seed);
+                }
+                "-c" | "--config" => options.config = Some(PathBuf::from(value()?)),
+                unknown => return Err(format!("unknown argument {}. See --help", unknown).into()),
+            }
+        }
+
+        return Ok(options);
+    }
+}
+
+/// The text shown for `--help`
+pub fn help() -> String {
+    let mut help = String::from(
+        "Usage: minesweeper [OPTIONS]\n\
+         \n\
+         Modes:\n  \
+           -g, --gui                 play in a window (default)\n  \
+           -t, --terminal            play in the terminal, one prompt at a time\n  \
+           -u, --tui                 play full-screen in the terminal with a cursor\n  \
+           -a, --accessible          play in the terminal with a screen reader, no grid art\n\
+         \n\
+         Options:\n  \
+           -d, --difficulty <NAME>   the board size and mine density, see Difficulties\n  \
+           -s, --seed <NUMBER>       generate the same boards every time\n  \
+           -c, --config <PATH>       the gui's settings file instead of minesweeper.ini in the config directory\n  \
+           -h, --help                show this message\n\
+         \n\
+         Difficulties:\n",
+    );
+    for difficulty in Difficulty::ALL {
+        let default = if difficulty == Difficulty::default() {
+            " (default)"
+        } else {
+            ""
+        };
+        // writing to a String can't fail
+        let _ = writeln!(
+            help,
+            "  {:<14}{}x{}, {}% mines{}",
+            difficulty.to_string(),
+            difficulty.width(),
+            difficulty.height(),
+            difficulty.is_mine_percentage() * 100.0,
+            default
+        );
+    }
+    return help;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(arguments: &[&str]) -> Result<Options, String> {
+        return Options::parse(arguments.iter().map(|argument| argument.to_string()))
+            .map_err(|error| error.to_string());
+    }
+
+    #[test]
+    fn values_follow_a_space_or_an_equals_sign() {
+        for arguments in [
+            [
+                "--difficulty",
+                "expert",
+                "--seed",
+                "42",
+                "--config",
+                "a.ini",
+            ]
+            .as_slice(),
+            &["--difficulty=expert", "--seed=42", "--config=a.ini"],
+            &["-d", "expert", "-s=42", "-c", "a.ini"],
+        ] {
+            let options = parse(arguments).unwrap();
+            assert_eq!(options.difficulty, Difficulty::Expert, "{:?}", arguments);
+            assert_eq!(options.seed, Some(42), "{:?}", arguments);
+            assert_eq!(
+                options.config,
+                Some(PathBuf::from("a.ini")),
+                "{:?}",
+                arguments
+            );
+        }
+    }
+
+    #[test]
+    fn short_flags_pick_the_mode() {
+        for (flag, mode) in [
+            ("-g", Mode::Gui),
+            ("-t", Mode::Terminal),
+            ("-u", Mode::Tui),
+            ("-a", Mode::Accessible),
+        ] {
+            assert_eq!(parse(&[flag]).unwrap().mode, mode);
+        }
+        assert!(parse(&["-h"]).unwrap().help);
+        // the last mode wins
+        assert_eq!(parse(&["-t", "--tui"]).unwrap().mode, Mode::Tui);
+    }
+
+    #[test]
+    fn no_arguments_are_the_defaults() {
+        let options = parse(&[]).unwrap();
+        assert_eq!(options.mode, Mode::default());
+        assert_eq!(options.difficulty, Difficulty::default());
+        assert_eq!(options.seed, None);
+        assert_eq!(options.config, None);
+        assert!(!options.help);
+    }
+
+    #[test]
+    fn bad_arguments_are_errors() {
+        assert_eq!(parse(&["--seed"]).unwrap_err(), "--seed needs a value");
+        assert_eq!(parse(&["-t", "-d"]).unwrap_err(), "-d needs a value");
+        assert_eq!(
+            parse(&["--seed", "-1"]).unwrap_err(),
+            "-1 is not a valid seed"
+        );
+        assert_eq!(
+            parse(&["--seed=lots"]).unwrap_err(),
+            "lots is not a valid seed"
+        );
+        assert_eq!(
+            parse(&["--fast"]).unwrap_err(),
+            "unknown argument --fast. See --help"
+        );
+        assert_eq!(
+            parse(&["--gui=yes"]).unwrap_err(),
+            "--gui doesn't take a value"
+        );
+        assert!(parse(&["--difficulty", "impossible"]).is_err());
+    }
+}
@@ -0,0 +1,306 @@
+//! The one-line command syntax used by text front ends.
+//!
+//! A line holds one or more commands separated by `;`, for example `r C7; f 8 2`.
+//! - `r CELL` reveals, `f` flags, `u` unflags and `c` chords a cell. The whole word works too
+//! - A cell is either a [label](minesweeper::label) like `C7`, or a row and column number from 1 like `7 3`, the same cell as `C7`
+//! - `undo`, `hint`, `new` and `quit` take no cell
+
+use std::{fmt::Display, ops::Range};
+
+use minesweeper::{
+    game::{Action, Command},
+    label::{cell_label, parse_cell_label},
+};
+
+/// A short description of the syntax, shown to players
+pub const SYNTAX_HELP: &str =
+    "Commands: r CELL (reveal), f (flag), u (unflag), c (chord), undo, hint, new, quit\n\
+A CELL is a label like C7, or a row and column number from 1 like 7 3. Separate commands with ;  e.g. r C7; f 8 2";
+
+/// An error in a command line, pointing at the token that caused it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The byte range of the bad token in the line. Empty at the end of the line when a token is missing
+    pub span: Range<usize>,
+    pub message: String,
+}
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{}", self.message);
+    }
+}
+impl std::error::Error for ParseError {}
+impl ParseError {
+    fn new(span: Range<usize>, message: impl Into<String>) -> Self {
+        return Self {
+            span,
+            message: message.into(),
+        };
+    }
+
+    /// Returns the `line` with the bad token underlined by carets, followed by the message
+    pub fn report(&self, line: &str) -> String {
+        let start = line[..self.span.start].chars().count();
+        let width = line[self.span.clone()].chars().count().max(1);
+        return format!(
+            "{}\n{}{} {}",
+            line,
+            " ".repeat(start),
+            "^".repeat(width),
+            self.message
+        );
+    }
+}
+
+/// A word of the command line and where it is
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    text: &'a str,
+    span: (usize, usize),
+}
+impl Token<'_> {
+    fn span(&self) -> Range<usize> {
+        return self.span.0..self.span.1;
+    }
+}
+
+/// Splits `line` into whitespace separated tokens. `;` is always a token of its own
+fn tokenize(line: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+
+    for (index, character) in line.char_indices() {
+        if character.is_whitespace() || character == ';' {
+            if let Some(start) = start.take() {
+                tokens.push(Token {
+                    text: &line[start..index],
+                    span: (start, index),
+                });
+            }
+            if character == ';' {
+                tokens.push(Token {
+                    text: ";",
+                    span: (index, index + 1),
+                });
+            }
+        } else if start.is_none() {
+            start = Some(index);
+        }
+    }
+    if let Some(start) = start {
+        tokens.push(Token {
+            text: &line[start..],
+            span: (start, line.len()),
+        });
+    }
+
+    return tokens;
+}
+
+/// Parses every command on a `line` for a `W`x`H` board. An empty line has no commands
+/// # Errors
+/// - When a command is unknown
+/// - When a cell is missing, isn't a number or is off the board
+/// - When a command is followed by something other than `;`
+pub fn parse<const W: usize, const H: usize>(line: &str) -> Result<Vec<Command>, ParseError> {
+    let tokens = tokenize(line);
+    let mut commands = Vec::new();
+
+    for tokens in tokens.split(|token| token.text == ";") {
+        let Some((name, arguments)) = tokens.split_first() else {
+            continue;
+        };
+
+        let (command, used) = match name.text.to_lowercase().as_str() {
+            "undo" => (Some(Command::Undo), 0),
+            "hint" => (Some(Command::Hint), 0),
+            "n" | "new" => (Some(Command::NewGame), 0),
+            "q" | "quit" => (Some(Command::Quit), 0),
+            // cancelling has no command, but is still checked like one
+            "cancel" => (None, 0),
+            word => {
+                let action = word.parse::<Action>().map_err(|_| {
+                    return ParseError::new(
+                        name.span(),
+                        format!(
+                            "unknown command {}, expected r, f, u, c, undo, hint, new or quit",
+                            name.text
+                        ),
+                    );
+                })?;
+                let (cell_index, used) = parse_cell::<W, H>(arguments, name.span.1)?;
+                (Some(Command::Act { action, cell_index }), used)
+            }
+        };
+
+        if let Some(extra) = arguments.get(used) {
+            return Err(ParseError::new(
+                extra.span(),
+                format!("unexpected {}, separate commands with ;", extra.text),
+            ));
+        }
+        commands.extend(command);
+    }
+
+    return Ok(commands);
+}
+
+/// Parses the cell at the start of `arguments`, either a label or a row and column number.
+/// Returns the cell's index and how many tokens it used
+fn parse_cell<const W: usize, const H: usize>(
+    arguments: &[Token],
+    previous_end: usize,
+) -> Result<((usize, usize), usize), ParseError> {
+    let Some(first) = arguments.first() else {
+        return Err(ParseError::new(
+            previous_end..previous_end,
+            "expected a cell, like C7 or 7 3",
+        ));
+    };
+
+    if first.text.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        let label = first;
+        let cell_index = parse_cell_label(label.text).ok_or_else(|| {
+            return ParseError::new(
+                label.span(),
+                format!("{} is not a cell label like C7", label.text),
+            );
+        })?;
+        if cell_index.0 >= H || cell_index.1 >= W {
+            return Err(ParseError::new(
+                label.span(),
+                format!(
+                    "{} is off the board, which goes from {} to {}",
+                    label.text,
+                    cell_label((0, 0)),
+                    cell_label((H - 1, W - 1))
+                ),
+            ));
+        }
+        return Ok((cell_index, 1));
+    }
+
+    let row_index = parse_number(Some(first), "row", H, previous_end)?;
+    let column_index = parse_number(arguments.get(1), "column", W, first.span.1)?;
+    return Ok(((row_index, column_index), 2));
+}
+
+/// Parses a row or column number from 1 to `length` into its index. When there is no token, the error points just after `previous_end`
+fn parse_number(
+    token: Option<&Token>,
+    name: &str,
+    length: usize,
+    previous_end: usize,
+) -> Result<usize, ParseError> {
+    let token = token.ok_or_else(|| {
+        return ParseError::new(
+            previous_end..previous_end,
+            format!("expected a {} number", name),
+        );
+    })?;
+    let number = token.text.parse::<usize>().map_err(|_| {
+        return ParseError::new(
+            token.span(),
+            format!("{} is not a {} number", token.text, name),
+        );
+    })?;
+    if number == 0 || number > length {
+        return Err(ParseError::new(
+            token.span(),
+            format!(
+                "{} {} is off the board, which has {}s 1 to {}",
+                name, number, name, length
+            ),
+        ));
+    }
+    return Ok(number - 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn act(action: Action, cell_index: (usize, usize)) -> Command {
+        return Command::Act { action, cell_index };
+    }
+
+    #[test]
+    fn parses_numbers_labels_and_batches() {
+        assert_eq!(
+            parse::<9, 9>("r 4 5; f 9 3;c C7").unwrap(),
+            vec![
+                act(Action::Reveal, (3, 4)),
+                act(Action::Flag, (8, 2)),
+                act(Action::Chord, (6, 2)),
+            ]
+        );
+        assert_eq!(
+            parse::<9, 9>("UNDO;hint; new ;quit").unwrap(),
+            vec![
+                Command::Undo,
+                Command::Hint,
+                Command::NewGame,
+                Command::Quit
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_commands_are_skipped() {
+        assert_eq!(parse::<9, 9>("").unwrap(), vec![]);
+        assert_eq!(parse::<9, 9>(" ;; cancel ;").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn errors_point_at_the_bad_token() {
+        let span = |line| parse::<9, 9>(line).unwrap_err().span;
+        assert_eq!(span("r 1 1; jump 2 2"), 7..11);
+        assert_eq!(span("f 1 10"), 4..6);
+        assert_eq!(span("f 0 1"), 2..3);
+        assert_eq!(span("r J1"), 2..4);
+        assert_eq!(span("r C0"), 2..4);
+        assert_eq!(span("r x 1"), 2..3);
+        assert_eq!(span("r 99999999999999999999 1"), 2..22);
+        assert_eq!(span("undo 3"), 5..6);
+        assert_eq!(span("cancel junk"), 7..11);
+        assert_eq!(span("r C7 D7"), 5..7);
+    }
+
+    #[test]
+    fn missing_cells_point_after_the_last_token() {
+        assert_eq!(parse::<9, 9>("r").unwrap_err().span, 1..1);
+        assert_eq!(parse::<9, 9>("r 3 ; f 1 1").unwrap_err().span, 3..3);
+    }
+
+    #[test]
+    fn off_board_labels_name_the_corners() {
+        let error = parse::<9, 9>("r J1").unwrap_err();
+        assert_eq!(
+            error.message,
+            "J1 is off the board, which goes from A1 to I9"
+        );
+    }
+
+    #[test]
+    fn numbers_count_from_one_like_labels() {
+        assert_eq!(
+            parse::<9, 9>("r 7 3").unwrap(),
+            parse::<9, 9>("r C7").unwrap()
+        );
+        assert_eq!(
+            parse::<9, 9>("f 9 9").unwrap(),
+            vec![act(Action::Flag, (8, 8))]
+        );
+        assert_eq!(
+            parse::<9, 9>("f 0 1").unwrap_err().message,
+            "row 0 is off the board, which has rows 1 to 9"
+        );
+    }
+
+    #[test]
+    fn report_underlines_the_span() {
+        let line = "f 1 x";
+        let error = parse::<9, 9>(line).unwrap_err();
+        assert_eq!(error.report(line), "f 1 x\n    ^ x is not a column number");
+    }
+}
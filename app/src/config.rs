@@ -0,0 +1,369 @@
+//! The config file, read at startup by front ends that have settings.
+//!
+//! It holds `key = value` lines, grouped under `[section]` headers. Lines before the first header are in the unnamed section `""`.
+//! Blank lines and lines starting with `#` are ignored, for example:
+//! ```text
+//! theme = mine
+//!
+//! [theme mine]
+//! base = dark
+//! background = #203040
+//! ```
+
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// The file name of the config file, see [default_path]
+pub const FILE_NAME: &str = "minesweeper.ini";
+
+/// Returns where the config file is kept: `$XDG_CONFIG_HOME/minesweeper.ini`, `~/.config/minesweeper.ini`, or `%APPDATA%\minesweeper.ini` on Windows.
+/// Returns [None] if none of those environment variables are set
+pub fn default_path() -> Option<PathBuf> {
+    let non_empty = |name| std::env::var_os(name).filter(|value| !value.is_empty());
+    let directory = non_empty("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| non_empty("HOME").map(|home| Path::new(&home).join(".config")))
+        .or_else(|| non_empty("APPDATA").map(PathBuf::from))?;
+    return Some(directory.join(FILE_NAME));
+}
+
+/// The `key = value` lines under one `[section]` header, in file order
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Section {
+    pub name: String,
+    pub entries: Vec<(String, String)>,
+}
+impl Section {
+    /// Returns the value of the last `key` in this section
+    pub fn get(&self, key: &str) -> Option<&str> {
+        return self
+            .entries
+            .iter()
+            .rev()
+            .find(|(other, _)| other == key)
+            .map(|(_, value)| value.as_str());
+    }
+}
+
+/// A parsed config file. Writing it with [Display] and parsing it again gives the same [Config]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Config {
+    sections: Vec<Section>,
+}
+impl Config {
+    /// Reads the config file at `path`. A missing file is an empty [Config]
+    /// # Errors
+    /// - When the file can't be read
+    /// - When a line is neither a header nor `key = value`
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        return match std::fs::read_to_string(path) {
+            Ok(text) => text.parse(),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(error) => Err(error.into()),
+        };
+    }
+
+    /// Returns every section, in file order
+    pub fn sections(&self) -> impl Iterator<Item = &Section> {
+        return self.sections.iter();
+    }
+
+    /// Returns the section called `name`
+    pub fn section(&self, name: &str) -> Option<&Section> {
+        return self.sections.iter().find(|section| section.name == name);
+    }
+
+    /// Returns the value of `key` in the section called `section`
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        return self.section(section)?.get(key);
+    }
+
+    /// Sets `key` to `value` in the section called `section`, adding either if they don't exist yet
+    pub fn set(&mut self, section: &str, key: &str, value: impl Into<String>) {
+        let index = match self.sections.iter().position(|other| other.name == section) {
+            Some(index) => index,
+            None => {
+                let section = Section {
+                    name: section.to_string(),
+                    entries: Vec::new(),
+                };
+                // the unnamed section has no header, so it has to come first
+                if section.name.is_empty() {
+                    self.sections.insert(0, section);
+                    0
+                } else {
+                    self.sections.push(section);
+                    self.sections.len() - 1
+                }
+            }
+        };
+        let entries = &mut self.sections[index].entries;
+        let value = value.into();
+        match entries.iter_mut().rev().find(|(other, _)| other == key) {
+            Some((_, old)) => *old = value,
+            None => entries.push((key.to_string(), value)),
+        }
+    }
+}
+
+/// Sets each `(key, value)` of `entries` in the section called `section` of the config file at `path`, keeping the rest of the file as it is, comments included.
+/// A missing file is created, along with its directory
+/// # Errors
+/// - When the file can't be read or written
+/// - When the file can't be parsed, so a file with a mistake in it isn't written over
+pub fn update(
+    path: &Path,
+    section: &str,
+    entries: &[(&str, &str)],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(error) => return Err(error.into()),
+    };
+    text.parse::<Config>()?;
+
+    for (key, value) in entries {
+        text = set_line(&text, section, key, value);
+    }
+    if let Some(directory) = path.parent() {
+        std::fs::create_dir_all(directory)?;
+    }
+    std::fs::write(path, text)?;
+    return Ok(());
+}
+
+/// Sets `key` to `value` in the section called `section` of the config file `text`, and returns the new text. Every other line is kept.
+/// - The last `key` line of the section is rewritten
+/// - Otherwise the key is added after the section's last line, or in a new section at the end
+fn set_line(text: &str, section: &str, key: &str, value: &str) -> String {
+    let mut lines: Vec<String> = text.lines().map(String::from).collect();
+    let mut current = "";
+    let mut key_line = None;
+    // where a new key goes, after the section's header or last entry
+    let mut end_of_section = None;
+    let mut first_header = None;
+
+    for (line_index, line) in lines.iter().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[') {
+            current = name.strip_suffix(']').unwrap_or(name).trim();
+            first_header.get_or_insert(line_index);
+            if current == section {
+                end_of_section = Some(line_index + 1);
+            }
+            continue;
+        }
+        if current == section {
+            end_of_section = Some(line_index + 1);
+            if line
+                .split_once('=')
+                .is_some_and(|(other, _)| other.trim() == key)
+            {
+                key_line = Some(line_index);
+            }
+        }
+    }
+    // the unnamed section has no header, it is everything before the first one
+    if section.is_empty() && end_of_section.is_none() {
+        end_of_section = Some(first_header.unwrap_or(lines.len()));
+    }
+
+    let entry = format!("{} = {}", key, value);
+    match (key_line, end_of_section) {
+        (Some(line_index), _) => lines[line_index] = entry,
+        (None, Some(line_index)) => lines.insert(line_index, entry),
+        (None, None) => {
+            if lines.last().is_some_and(|line| !line.trim().is_empty()) {
+                lines.push(String::new());
+            }
+            lines.push(format!("[{}]", section));
+            lines.push(entry);
+        }
+    }
+
+    let mut text = lines.join("\n");
+    text.push('\n');
+    return text;
+}
+
+impl FromStr for Config {
+    type Err = Box<dyn std::error::Error>;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut config = Config::default();
+        let mut section = String::new();
+
+        for (line_index, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[') {
+                let name = name.strip_suffix(']').ok_or(format!(
+                    "line {}: expected ] to end the header",
+                    line_index + 1
+                ))?;
+                section = name.trim().to_string();
+                if config.section(&section).is_none() {
+                    config.sections.push(Section {
+                        name: section.clone(),
+                        entries: Vec::new(),
+                    });
+                }
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or(format!("line {}: expected key = value", line_index + 1))?;
+            config.set(&section, key.trim(), value.trim());
+        }
+
+        return Ok(config);
+    }
+}
+impl Display for Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, section) in self.sections.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            if !section.name.is_empty() {
+                writeln!(f, "[{}]", section.name)?;
+            }
+            for (key, value) in &section.entries {
+                writeln!(f, "{} = {}", key, value)?;
+            }
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "# my settings
+theme = mine
+volume = 50
+
+[theme mine]
+base = dark
+background = #203040
+";
+
+    #[test]
+    fn parses_sections_and_skips_comments() {
+        let config: Config = TEXT.parse().unwrap();
+        assert_eq!(config.get("", "theme"), Some("mine"));
+        assert_eq!(config.get("theme mine", "background"), Some("#203040"));
+        assert_eq!(config.get("theme mine", "theme"), None);
+        assert_eq!(config.get("theme other", "base"), None);
+        assert_eq!(config.sections().count(), 2);
+    }
+
+    #[test]
+    fn the_last_value_of_a_key_wins() {
+        let config: Config = "a = 1\n[s]\nb = 2\n[ s ]\nb = 3\n".parse().unwrap();
+        assert_eq!(config.get("", "a"), Some("1"));
+        assert_eq!(config.get("s", "b"), Some("3"));
+        assert_eq!(config.sections().count(), 2);
+    }
+
+    #[test]
+    fn values_can_contain_equals_signs() {
+        let config: Config = "key = a = b".parse().unwrap();
+        assert_eq!(config.get("", "key"), Some("a = b"));
+    }
+
+    #[test]
+    fn errors_name_the_line() {
+        let error = "a = 1\n[broken\n".parse::<Config>().unwrap_err();
+        assert_eq!(error.to_string(), "line 2: expected ] to end the header");
+        let error = "a = 1\n\njust words\n".parse::<Config>().unwrap_err();
+        assert_eq!(error.to_string(), "line 3: expected key = value");
+    }
+
+    #[test]
+    fn set_puts_the_unnamed_section_first() {
+        let mut config: Config = "[s]\nb = 2\n".parse().unwrap();
+        config.set("", "a", "1");
+        config.set("s", "b", "3");
+        config.set("t", "c", "4");
+        assert_eq!(config.to_string(), "a = 1\n\n[s]\nb = 3\n\n[t]\nc = 4\n");
+    }
+
+    #[test]
+    fn set_line_keeps_comments_and_other_keys() {
+        let text = set_line(TEXT, "", "volume", "75");
+        assert_eq!(text, TEXT.replace("volume = 50", "volume = 75"));
+        let text = set_line(&text, "", "mute", "true");
+        assert_eq!(
+            text,
+            TEXT.replace("volume = 50", "volume = 75\nmute = true")
+        );
+    }
+
+    #[test]
+    fn set_line_adds_missing_sections() {
+        assert_eq!(set_line("", "", "mute", "true"), "mute = true\n");
+        assert_eq!(
+            set_line("# comment\n[s]\nb = 2", "", "a", "1"),
+            "# comment\na = 1\n[s]\nb = 2\n"
+        );
+        assert_eq!(
+            set_line("a = 1\n# end", "s", "b", "2"),
+            "a = 1\n# end\n\n[s]\nb = 2\n"
+        );
+        assert_eq!(
+            set_line("[s]\n[t]\nc = 3", "s", "b", "2"),
+            "[s]\nb = 2\n[t]\nc = 3\n"
+        );
+    }
+
+    #[test]
+    fn set_line_rewrites_the_last_key() {
+        let text = "[s]\nb = 1\n[t]\nb = 1\n[s]\nb = 2\n";
+        assert_eq!(
+            set_line(text, "s", "b", "3"),
+            "[s]\nb = 1\n[t]\nb = 1\n[s]\nb = 3\n"
+        );
+        assert_eq!(
+            set_line(text, "s", "b", "3")
+                .parse::<Config>()
+                .unwrap()
+                .get("s", "b"),
+            Some("3")
+        );
+    }
+
+    #[test]
+    fn update_leaves_a_malformed_file_alone() {
+        let path = std::env::temp_dir().join(format!("minesweeper-{}.ini", std::process::id()));
+        std::fs::write(&path, "# settings\nnot a key\n").unwrap();
+        assert!(update(&path, "", &[("mute", "true")]).is_err());
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "# settings\nnot a key\n"
+        );
+
+        std::fs::write(&path, "# settings\n[theme mine]\nbase = dark\n").unwrap();
+        update(&path, "", &[("volume", "25"), ("mute", "true")]).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "# settings\nvolume = 25\nmute = true\n[theme mine]\nbase = dark\n"
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn writing_and_parsing_gives_the_same_config() {
+        let config: Config = TEXT.parse().unwrap();
+        assert_eq!(config.to_string().parse::<Config>().unwrap(), config);
+    }
+}
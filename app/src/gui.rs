@@ -0,0 +1,1022 @@
+//! The macroquad front end. It opens on a [main menu](Page::Main), Esc returns to it from a game
+
+mod animation;
+mod camera;
+mod hud;
+mod menu;
+mod pointer;
+mod skin;
+mod sound;
+mod theme;
+
+use std::{
+    convert::Infallible,
+    path::{Path, PathBuf},
+};
+
+use macroquad::prelude::*;
+
+use crate::config::{self, Config};
+pub use animation::Animations;
+pub use camera::Camera;
+use hud::Face;
+use menu::{Button, Menu, Record, Settings, Statistics};
+use minesweeper::{
+    frontend,
+    frontend::{CommandSource, Renderer},
+    game::{Action, Command, Difficulty, Event, Game, GameState, HEIGHT, WIDTH},
+};
+pub use pointer::{Pointer, Press};
+pub use skin::{Skin, Tile};
+pub use sound::Sounds;
+pub use theme::Theme;
+
+/// The fraction of the window the board fills along its tighter dimension
+pub const BOARD_FILL: f32 = 0.8;
+
+/// The percentage the volume button goes up by to the next multiple, back to this after 100
+const VOLUME_STEP: u8 = 25;
+
+/// Runs the menus and games in the macroquad window, starting with `difficulty` selected.
+/// New games are generated from `seed` if there is one. Themes are read from `config`, see [Theme::load_all],
+/// and sounds from the directory named by its `sounds` key, see [Sounds::load]. Relative paths start from the directory of `config_path`.
+/// The volume is saved to `config_path` when it changes, if there is one.
+/// Must be run by macroquad, see [macroquad::Window]
+pub async fn run(
+    difficulty: Difficulty,
+    seed: Option<u64>,
+    config: Config,
+    config_path: Option<PathBuf>,
+) {
+    let directory = config_path
+        .as_deref()
+        .and_then(Path::parent)
+        .unwrap_or(Path::new(""));
+    let sounds = match config.get("", "sounds") {
+        Some(sounds) => Sounds::load(&directory.join(sounds)).await,
+        None => Sounds::load(sound::DEFAULT_DIRECTORY.as_ref()).await,
+    };
+    let themes = Theme::load_all(&config, directory);
+    let mut app = App::new(difficulty, seed, &config, config_path, themes, sounds);
+
+    loop {
+        clear_background(app.theme().background);
+
+        if !app.update() {
+            return;
+        }
+
+        next_frame().await;
+    }
+}
+
+/// What the window shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Page {
+    Main,
+    Difficulty,
+    Statistics,
+    Settings,
+    Playing,
+}
+
+/// The state of the GUI between frames
+#[derive(Debug)]
+struct App {
+    page: Page,
+    menu: Menu,
+    difficulty: Difficulty,
+    seed: Option<u64>,
+    games: Games,
+    settings: Settings,
+    statistics: Statistics,
+    /// [Settings::theme] is an index into these
+    themes: Vec<Theme>,
+    animations: Animations,
+    sounds: Sounds,
+    camera: Camera,
+    pointer: Pointer,
+    /// the config file the volume is saved to
+    config_path: Option<PathBuf>,
+}
+impl App {
+    /// Starts on the main menu using the theme named by the `theme` key of `config`,
+    /// and the volume from its `volume` (a percentage) and `mute` (true or false) keys
+    fn new(
+        difficulty: Difficulty,
+        seed: Option<u64>,
+        config: &Config,
+        config_path: Option<PathBuf>,
+        themes: Vec<Theme>,
+        sounds: Sounds,
+    ) -> Self {
+        let mut settings = Settings::default();
+        if let Some(name) = config.get("", "theme") {
+            match themes.iter().position(|theme| theme.name == name) {
+                Some(index) => settings.theme = index,
+                None => eprintln!("warning: there is no theme {}", name),
+            }
+        }
+        if let Some(volume) = config.get("", "volume") {
+            match volume.parse::<u8>() {
+                Ok(volume) if volume <= 100 => settings.volume = volume,
+                _ => eprintln!("warning: volume {} is not a percentage", volume),
+            }
+        }
+        if let Some(mute) = config.get("", "mute") {
+            match mute.parse() {
+                Ok(mute) => settings.mute = mute,
+                Err(_) => eprintln!("warning: mute {} is not true or false", mute),
+            }
+        }
+
+        return Self {
+            page: Page::Main,
+            menu: Menu::default(),
+            difficulty,
+            seed,
+            games: Games::default(),
+            settings,
+            statistics: Statistics::default(),
+            themes,
+            animations: Animations::default(),
+            sounds,
+            camera: Camera::default(),
+            pointer: Pointer::default(),
+            config_path,
+        };
+    }
+
+    fn theme(&self) -> &Theme {
+        return &self.themes[self.settings.theme];
+    }
+
+    fn open(&mut self, page: Page) {
+        self.page = page;
+        self.menu.reset();
+    }
+
+    /// Shows the current [Page] for a frame. Returns false when the player quits
+    fn update(&mut self) -> bool {
+        let escape = is_key_pressed(KeyCode::Escape);
+        match self.page {
+            Page::Playing if escape => self.open(Page::Main),
+            Page::Playing => {
+                let (width, height) = (self.difficulty.width(), self.difficulty.height());
+                let cell_index = self.games.cell_index(self.difficulty).unwrap_or((0, 0));
+                self.camera.update(
+                    board_boundary(width, height),
+                    board_view(),
+                    cell_boundary(width, height, cell_index),
+                );
+                let record = self.statistics.record_mut(self.difficulty);
+                let theme = &self.themes[self.settings.theme];
+                let next_page = self.games.play(
+                    self.difficulty,
+                    record,
+                    &mut self.menu,
+                    Screen::new(
+                        self.settings,
+                        theme,
+                        &mut self.animations,
+                        &self.sounds,
+                        self.camera,
+                        self.pointer,
+                    ),
+                    Controls::new(self.settings, self.camera, &mut self.pointer),
+                );
+                if let Some(page) = next_page {
+                    self.open(page);
+                }
+            }
+            Page::Main if escape && self.games.is_playing(self.difficulty) => {
+                self.open(Page::Playing)
+            }
+            Page::Main => return self.main_menu(),
+            Page::Difficulty | Page::Statistics | Page::Settings if escape => self.open(Page::Main),
+            Page::Difficulty => self.difficulty_menu(),
+            Page::Statistics => self.statistics_page(),
+            Page::Settings => self.settings_menu(),
+        }
+        return true;
+    }
+
+    /// Returns false when the player picks quit
+    fn main_menu(&mut self) -> bool {
+        let buttons = [
+            Button::new("New game"),
+            Button::new("Continue").enabled(self.games.is_playing(self.difficulty)),
+            Button::new(format!("Difficulty: {}", self.difficulty)),
+            Button::new("Statistics"),
+            Button::new("Settings"),
+            Button::new("Quit"),
+        ];
+        let theme = &self.themes[self.settings.theme];
+        match self.menu.show(theme, "Minesweeper", &[], &buttons) {
+            Some(0) => {
+                self.games.start(self.difficulty, self.seed);
+                self.open(Page::Playing);
+            }
+            Some(1) => self.open(Page::Playing),
+            Some(2) => self.open(Page::Difficulty),
+            Some(3) => self.open(Page::Statistics),
+            Some(4) => self.open(Page::Settings),
+            Some(_) => return false,
+            None => {}
+        }
+        return true;
+    }
+
+    fn difficulty_menu(&mut self) {
+        let mut buttons: Vec<Button> = Difficulty::ALL
+            .iter()
+            .map(|difficulty| {
+                let selected = if *difficulty == self.difficulty {
+                    "> "
+                } else {
+                    ""
+                };
+                return Button::new(format!(
+                    "{}{} {}x{}",
+                    selected,
+                    difficulty,
+                    difficulty.width(),
+                    difficulty.height()
+                ));
+            })
+            .collect();
+        buttons.push(Button::new("Back"));
+
+        let theme = &self.themes[self.settings.theme];
+        if let Some(index) = self.menu.show(theme, "Difficulty", &[], &buttons) {
+            if let Some(&difficulty) = Difficulty::ALL.get(index) {
+                self.difficulty = difficulty;
+                // a different board, fit it to the window
+                self.camera = Camera::default();
+                self.pointer = Pointer::default();
+            }
+            self.open(Page::Main);
+        }
+    }
+
+    fn statistics_page(&mut self) {
+        let lines: Vec<String> = Difficulty::ALL
+            .iter()
+            .map(|&difficulty| {
+                let record = self.statistics.record(difficulty);
+                let best = record
+                    .best
+                    .map(|best| format!(", best {}s", best.as_secs()))
+                    .unwrap_or_default();
+                return format!(
+                    "{}: {} played, {} won{}",
+                    difficulty, record.played, record.won, best
+                );
+            })
+            .collect();
+
+        let theme = &self.themes[self.settings.theme];
+        if self
+            .menu
+            .show(theme, "Statistics", &lines, &[Button::new("Back")])
+            .is_some()
+        {
+            self.open(Page::Main);
+        }
+    }
+
+    fn settings_menu(&mut self) {
+        let on_off = |on| if on { "on" } else { "off" };
+        let buttons = [
+            Button::new(format!(
+                "Keyboard cursor: {}",
+                on_off(self.settings.show_cursor)
+            )),
+            Button::new(format!(
+                "Swap mouse buttons: {}",
+                on_off(self.settings.swap_buttons)
+            )),
+            Button::new(format!("Theme: {}", self.theme().name)),
+            Button::new(format!("Animations: {}", on_off(self.settings.animations))),
+            Button::new(format!("Sound: {}", on_off(!self.settings.mute))),
+            Button::new(format!("Volume: {}%", self.settings.volume)),
+            Button::new("Back"),
+        ];
+        let theme = &self.themes[self.settings.theme];
+        match self.menu.show(theme, "Settings", &[], &buttons) {
+            Some(0) => self.settings.show_cursor = !self.settings.show_cursor,
+            Some(1) => self.settings.swap_buttons = !self.settings.swap_buttons,
+            Some(2) => self.settings.theme = (self.settings.theme + 1) % self.themes.len(),
+            Some(3) => self.settings.animations = !self.settings.animations,
+            Some(4) => {
+                self.settings.mute = !self.settings.mute;
+                self.save_volume();
+            }
+            Some(5) => {
+                self.settings.volume = match self.settings.volume {
+                    100.. => VOLUME_STEP,
+                    volume => (volume / VOLUME_STEP + 1) * VOLUME_STEP,
+                };
+                self.save_volume();
+            }
+            Some(_) => self.open(Page::Main),
+            None => {}
+        }
+    }
+
+    /// Writes the volume and mute settings to the config file, keeping the rest of it. Warns if it can't be written
+    fn save_volume(&self) {
+        let Some(path) = &self.config_path else {
+            return;
+        };
+        let volume = self.settings.volume.to_string();
+        let mute = self.settings.mute.to_string();
+        if let Err(error) = config::update(path, "", &[("volume", &volume), ("mute", &mute)]) {
+            eprintln!("warning: can't save {}: {}", path.display(), error);
+        }
+    }
+}
+
+/// The game at each [Difficulty], which are different types because the board size is a const parameter
+#[derive(Debug, Default)]
+struct Games {
+    beginner: Option<Game<9, 9>>,
+    intermediate: Option<Game<16, 16>>,
+    expert: Option<Game<30, 16>>,
+    large: Option<Game<WIDTH, HEIGHT>>,
+}
+impl Games {
+    /// Returns true when there is an unfinished game at `difficulty` to continue
+    fn is_playing(&self, difficulty: Difficulty) -> bool {
+        let state = match difficulty {
+            Difficulty::Beginner => self.beginner.as_ref().map(Game::state),
+            Difficulty::Intermediate => self.intermediate.as_ref().map(Game::state),
+            Difficulty::Expert => self.expert.as_ref().map(Game::state),
+            Difficulty::Large => self.large.as_ref().map(Game::state),
+        };
+        return state == Some(GameState::Playing);
+    }
+
+    /// Returns the selected cell of the game at `difficulty`, if there is one
+    fn cell_index(&self, difficulty: Difficulty) -> Option<(usize, usize)> {
+        return match difficulty {
+            Difficulty::Beginner => self.beginner.as_ref().map(Game::cell_index),
+            Difficulty::Intermediate => self.intermediate.as_ref().map(Game::cell_index),
+            Difficulty::Expert => self.expert.as_ref().map(Game::cell_index),
+            Difficulty::Large => self.large.as_ref().map(Game::cell_index),
+        };
+    }
+
+    /// Replaces the game at `difficulty` with a new one
+    fn start(&mut self, difficulty: Difficulty, seed: Option<u64>) {
+        match difficulty {
+            Difficulty::Beginner => start(&mut self.beginner, difficulty, seed),
+            Difficulty::Intermediate => start(&mut self.intermediate, difficulty, seed),
+            Difficulty::Expert => start(&mut self.expert, difficulty, seed),
+            Difficulty::Large => start(&mut self.large, difficulty, seed),
+        }
+    }
+
+    /// Plays a frame of the game at `difficulty`, adding it to `record` if it finishes.
+    /// Returns the [Page] to open if the player leaves the game
+    fn play(
+        &mut self,
+        difficulty: Difficulty,
+        record: &mut Record,
+        menu: &mut Menu,
+        screen: Screen,
+        controls: Controls,
+    ) -> Option<Page> {
+        return match difficulty {
+            Difficulty::Beginner => play(&mut self.beginner, record, menu, screen, controls),
+            Difficulty::Intermediate => {
+                play(&mut self.intermediate, record, menu, screen, controls)
+            }
+            Difficulty::Expert => play(&mut self.expert, record, menu, screen, controls),
+            Difficulty::Large => play(&mut self.large, record, menu, screen, controls),
+        };
+    }
+}
+
+/// Starts the next game in `game`, or the first one at `difficulty` if there isn't one
+fn start<const W: usize, const H: usize>(
+    game: &mut Option<Game<W, H>>,
+    difficulty: Difficulty,
+    seed: Option<u64>,
+) {
+    match game {
+        Some(game) => game.restart(),
+        None => {
+            let is_mine_percentage = difficulty.is_mine_percentage();
+            *game = Some(match seed {
+                Some(seed) => Game::seeded(is_mine_percentage, seed),
+                None => Game::new(is_mine_percentage),
+            });
+        }
+    }
+}
+
+/// Plays a frame of `game` with [frontend::turn], adding it to `record` if it finishes.
+/// A finished game stays on screen with the [end_screen] until the player picks what to do next
+fn play<const W: usize, const H: usize>(
+    game: &mut Option<Game<W, H>>,
+    record: &mut Record,
+    menu: &mut Menu,
+    mut screen: Screen,
+    mut controls: Controls,
+) -> Option<Page> {
+    let game = game.as_mut()?;
+    let was_playing = game.state() == GameState::Playing;
+    let Ok(state) = frontend::turn(game, &mut screen, &mut controls);
+    if !state.is_finished() {
+        return None;
+    }
+    if was_playing {
+        record.add(state, game.elapsed());
+        menu.reset();
+        // the key or click that finished the game would also pick a button
+        return None;
+    }
+    return end_screen(game, menu, screen.theme);
+}
+
+/// Shows how `game` ended above the board, with buttons under it to play again or return to the menu.
+/// Returns the [Page] to open if the player leaves the game
+fn end_screen<const W: usize, const H: usize>(
+    game: &mut Game<W, H>,
+    menu: &mut Menu,
+    theme: &Theme,
+) -> Option<Page> {
+    let cell_boundaries = cell_boundaries::<W, H>();
+    let (top, bottom) = (
+        cell_boundaries[0][0].top(),
+        cell_boundaries[H - 1][0].bottom(),
+    );
+    let unit = screen_height() / 16.0;
+
+    let elapsed = game.elapsed().as_secs_f32();
+    let three_bv = game.board().three_bv();
+    let message = match game.state() {
+        GameState::Won => format!(
+            "You cleared the board!  Time: {:.0}s  3BV: {}  3BV/s: {:.2}",
+            elapsed,
+            three_bv,
+            three_bv as f32 / elapsed.max(1.0)
+        ),
+        _ => format!(
+            "You revealed a mine.  Time: {:.0}s  3BV: {}",
+            elapsed, three_bv
+        ),
+    };
+    theme.draw_centered_text(
+        &message,
+        screen_width() / 2.0,
+        (hud::boundary().bottom() + top) / 2.0,
+        unit * 0.7,
+        theme.text,
+    );
+
+    let buttons = [Button::new("Play again"), Button::new("Menu")];
+    return match menu.show_row(theme, (bottom + screen_height()) / 2.0, &buttons) {
+        Some(0) => {
+            game.apply(Command::NewGame);
+            None
+        }
+        Some(_) => Some(Page::Main),
+        None => None,
+    };
+}
+
+/// The boundary of a board `width` by `height` cells, centered and fit to the window below the [hud]. The [Camera] moves it from here
+fn board_boundary(width: usize, height: usize) -> Rect {
+    let view = board_view();
+    let cell_size = (view.w / width as f32).min(view.h / height as f32) * BOARD_FILL;
+    let size = Vec2::new(width as f32, height as f32) * cell_size;
+    let position = view.point() + (view.size() - size) / 2.0;
+    return Rect::new(position.x, position.y, size.x, size.y);
+}
+
+/// The part of the window below the [hud], where the board is seen
+fn board_view() -> Rect {
+    let hud = hud::boundary();
+    return Rect::new(0.0, hud.bottom(), screen_width(), screen_height() - hud.h);
+}
+
+/// The boundary of the cell at `cell_index` on a `width`x`height` board that fits the window
+fn cell_boundary(width: usize, height: usize, cell_index: (usize, usize)) -> Rect {
+    let board = board_boundary(width, height);
+    let cell_size = board.w / width as f32;
+    let (row_index, column_index) = cell_index;
+    return Rect::new(
+        board.x + column_index as f32 * cell_size,
+        board.y + row_index as f32 * cell_size,
+        cell_size,
+        cell_size,
+    );
+}
+
+fn cell_size<const W: usize, const H: usize>() -> f32 {
+    return board_boundary(W, H).w / W as f32;
+}
+
+fn cell_boundaries<const W: usize, const H: usize>() -> [[Rect; W]; H] {
+    let mut boundaries = [[Rect::default(); W]; H];
+
+    let cell_size: f32 = cell_size::<W, H>();
+
+    let board_position = board_boundary(W, H).point();
+
+    for (row_index, row) in boundaries.iter_mut().enumerate() {
+        for (column_index, boundary) in row.iter_mut().enumerate() {
+            let cell_position =
+                board_position + (cell_size * Vec2::new(column_index as f32, row_index as f32));
+
+            *boundary = Rect::new(cell_position.x, cell_position.y, cell_size, cell_size);
+        }
+    }
+
+    return boundaries;
+}
+
+/// Draws the board in the macroquad window and plays its sounds
+#[derive(Debug)]
+pub struct Screen<'a> {
+    settings: Settings,
+    theme: &'a Theme,
+    animations: &'a mut Animations,
+    sounds: &'a Sounds,
+    camera: Camera,
+    /// the hovered and pressed cells as of the last frame
+    pointer: Pointer,
+}
+impl<'a> Screen<'a> {
+    pub fn new(
+        settings: Settings,
+        theme: &'a Theme,
+        animations: &'a mut Animations,
+        sounds: &'a Sounds,
+        camera: Camera,
+        pointer: Pointer,
+    ) -> Self {
+        return Self {
+            settings,
+            theme,
+            animations,
+            sounds,
+            camera,
+            pointer,
+        };
+    }
+}
+impl Renderer for Screen<'_> {
+    type Error = Infallible;
+    /// Starts [Animations] for what changed since the last frame. Any key or click skips them
+    fn render<const W: usize, const H: usize>(
+        &mut self,
+        game: &Game<W, H>,
+    ) -> Result<(), Self::Error> {
+        let now = get_time();
+        let origin = game.last_move().unwrap_or(game.cell_index());
+        self.animations.update(tiles(game), W, origin, now);
+        let is_skipped = !get_keys_pressed().is_empty()
+            || [MouseButton::Left, MouseButton::Right, MouseButton::Middle]
+                .into_iter()
+                .any(is_mouse_button_pressed);
+        if !self.settings.animations || (is_skipped && self.animations.is_running(now)) {
+            self.animations.skip();
+        }
+
+        draw(game, self, now);
+        return Ok(());
+    }
+
+    /// Plays the sound of each [Event] unless sound is muted
+    fn react(&mut self, events: Vec<Event>) -> Result<(), Self::Error> {
+        if self.settings.mute {
+            return Ok(());
+        }
+        for event in events {
+            self.sounds.play(event, self.settings.volume as f32 / 100.0);
+        }
+        return Ok(());
+    }
+}
+
+/// Turns key presses and mouse clicks into [Command]s. A finished game ignores them, the [end_screen] takes over
+#[derive(Debug)]
+pub struct Controls<'a> {
+    settings: Settings,
+    /// clicks are on the board as the camera draws it
+    camera: Camera,
+    /// the press being held, across frames
+    pointer: &'a mut Pointer,
+}
+impl<'a> Controls<'a> {
+    pub fn new(settings: Settings, camera: Camera, pointer: &'a mut Pointer) -> Self {
+        return Self {
+            settings,
+            camera,
+            pointer,
+        };
+    }
+}
+impl CommandSource for Controls<'_> {
+    type Error = Infallible;
+    fn next_command<const W: usize, const H: usize>(
+        &mut self,
+        game: &Game<W, H>,
+    ) -> Result<Option<Command>, Self::Error> {
+        // the pointer follows the mouse every frame, even when a key or the face button is used, so no release is missed
+        let (primary, secondary) = mouse_buttons(self.settings);
+        let click = self
+            .pointer
+            .update(hovered_cell::<W, H>(self.camera), primary, secondary);
+
+        if hud::is_face_clicked(primary) {
+            return Ok(Some(Command::NewGame));
+        }
+        if game.state().is_finished() {
+            return Ok(None);
+        }
+        // the release that makes a click is only seen on this frame, so it goes before any key
+        let click = click.and_then(|(cell_index, press)| handle_click(game, cell_index, press));
+        return Ok(click.or_else(|| handle_keys(game)));
+    }
+}
+
+/// Returns the [Tile] each cell shows, row by row. A finished game shows what every cell turned out to be
+fn tiles<const W: usize, const H: usize>(game: &Game<W, H>) -> Vec<Tile> {
+    let is_finished = game.state().is_finished();
+    return game
+        .board()
+        .cells()
+        .iter()
+        .flatten()
+        .map(|cell| match is_finished {
+            true => Tile::of_outcome(cell.outcome()),
+            false => Tile::of_cell(cell),
+        })
+        .collect();
+}
+
+fn draw<const W: usize, const H: usize>(game: &Game<W, H>, screen: &Screen, now: f64) {
+    let Screen {
+        settings,
+        theme,
+        ref animations,
+        camera,
+        pointer,
+        ..
+    } = *screen;
+    let cell_boundaries = cell_boundaries::<W, H>();
+    let cell_size: f32 = cell_size::<W, H>() * camera.zoom();
+    let border_size = cell_size * 0.05;
+    let tiles = tiles(game);
+    let explosion = animations.explosion(now);
+
+    // shake the board while the explosion lasts
+    let shake = match explosion {
+        Some((_, progress)) => {
+            let time = now as f32;
+            Vec2::new((time * 60.0).sin(), (time * 47.0).cos())
+                * cell_size
+                * 0.15
+                * (1.0 - progress)
+        }
+        None => Vec2::ZERO,
+    };
+
+    // cells held down by the mouse look revealed, and a chord shows the hidden neighbors it would reveal
+    let is_finished = game.state().is_finished();
+    let held: Vec<(usize, usize)> = match pointer.pressed() {
+        _ if is_finished => Vec::new(),
+        Some((cell_index, Press::Primary)) => vec![cell_index],
+        Some((cell_index, Press::Both)) => std::iter::once(cell_index)
+            .chain(game.board().hidden_neighbors(cell_index))
+            .collect(),
+        Some((_, Press::Secondary)) | None => Vec::new(),
+    };
+
+    for (row_index, row) in cell_boundaries.into_iter().enumerate() {
+        for (column_index, boundary) in row.into_iter().enumerate() {
+            let boundary = camera.apply(boundary).offset(shake);
+            let index = row_index * W + column_index;
+            let tile = match tiles[index] {
+                Tile::Hidden if held.contains(&(row_index, column_index)) => Tile::Revealed,
+                tile => tile,
+            };
+
+            match animations.progress(index, now) {
+                None => draw_cell(tile, boundary, border_size, theme),
+                // the cell still looks hidden until its effect starts, then grows from the center
+                Some((effect, progress)) => {
+                    draw_cell(Tile::Hidden, boundary, border_size, theme);
+                    if progress > 0.0 {
+                        let size = boundary.size() * effect.scale(progress);
+                        let grown = Rect::new(
+                            boundary.center().x - size.x / 2.0,
+                            boundary.center().y - size.y / 2.0,
+                            size.x,
+                            size.y,
+                        );
+                        draw_cell(tile, grown, border_size * effect.scale(progress), theme);
+                    }
+                }
+            }
+
+            // light up the hidden cell under the mouse
+            if !is_finished
+                && held.is_empty()
+                && matches!(tile, Tile::Hidden | Tile::Flag)
+                && pointer.hovered() == Some((row_index, column_index))
+            {
+                draw_rectangle(
+                    boundary.x,
+                    boundary.y,
+                    boundary.w,
+                    boundary.h,
+                    Color {
+                        a: 0.3,
+                        ..theme.revealed
+                    },
+                );
+            }
+
+            // outline the keyboard cursor
+            if settings.show_cursor
+                && !is_finished
+                && (row_index, column_index) == game.cell_index()
+            {
+                draw_rectangle_lines(
+                    boundary.x,
+                    boundary.y,
+                    boundary.w,
+                    boundary.h,
+                    border_size * 3.0,
+                    theme.cursor,
+                );
+            }
+        }
+    }
+
+    // a fading fireball over the detonated mine
+    if let Some(((row_index, column_index), progress)) = explosion {
+        let center = camera
+            .apply(cell_boundaries[row_index][column_index])
+            .offset(shake)
+            .center();
+        let fade = 1.0 - progress;
+        draw_circle(
+            center.x,
+            center.y,
+            cell_size * (0.5 + 2.5 * progress),
+            Color {
+                a: 0.6 * fade,
+                ..theme.mine
+            },
+        );
+        draw_circle(
+            center.x,
+            center.y,
+            cell_size * (0.3 + 1.2 * progress),
+            Color {
+                a: 0.8 * fade,
+                ..theme.pennant
+            },
+        );
+    }
+
+    // the bar stays on top of a zoomed or panned board
+    let (primary, secondary) = mouse_buttons(settings);
+    let is_pressing = pointer.hovered().is_some()
+        && (is_mouse_button_down(primary) || is_mouse_button_down(secondary));
+    hud::draw(
+        game.remaining_mines(),
+        game.elapsed(),
+        Face::of(game.state(), is_pressing),
+        primary,
+        theme,
+    );
+}
+
+/// Draws a [Tile] over `boundary`, from the theme's [Skin] if it has one.
+/// Otherwise it is a colored rectangle inside a border, with its glyph on top
+fn draw_cell(tile: Tile, boundary: Rect, border_size: f32, theme: &Theme) {
+    if let Some(skin) = &theme.skin {
+        skin.draw(tile, boundary);
+        return;
+    }
+    let color = theme.tile_color(tile);
+
+    // draw border
+    draw_rectangle(boundary.x, boundary.y, boundary.w, boundary.h, theme.border);
+
+    // draw interior
+    let interior = Rect::new(
+        boundary.x + border_size,
+        boundary.y + border_size,
+        boundary.w - 2.0 * border_size,
+        boundary.h - 2.0 * border_size,
+    );
+    draw_rectangle(interior.x, interior.y, interior.w, interior.h, color);
+    if matches!(tile, Tile::Hidden | Tile::Flag | Tile::Mine) {
+        draw_bevel(interior, color);
+    }
+
+    draw_glyph(tile, interior, theme);
+}
+
+/// Draws a raised edge inside a hidden cell's `interior`, so hidden and revealed cells differ by more than `color`
+fn draw_bevel(interior: Rect, color: Color) {
+    let thickness = interior.w * 0.08;
+    let light = Color::from_vec(color.to_vec().lerp(WHITE.to_vec(), 0.5));
+    let dark = Color::from_vec(color.to_vec().lerp(BLACK.to_vec(), 0.4));
+    draw_rectangle(interior.x, interior.y, interior.w, thickness, light);
+    draw_rectangle(interior.x, interior.y, thickness, interior.h, light);
+    draw_rectangle(
+        interior.x,
+        interior.bottom() - thickness,
+        interior.w,
+        thickness,
+        dark,
+    );
+    draw_rectangle(
+        interior.right() - thickness,
+        interior.y,
+        thickness,
+        interior.h,
+        dark,
+    );
+}
+
+/// Draws a cross in the mine color centered on `center`, scaled to a cell `size` wide
+fn draw_cross(center: Vec2, size: f32, theme: &Theme) {
+    let (half, thickness) = (size * 0.4, size * 0.08);
+    draw_line(
+        center.x - half,
+        center.y - half,
+        center.x + half,
+        center.y + half,
+        thickness,
+        theme.mine,
+    );
+    draw_line(
+        center.x - half,
+        center.y + half,
+        center.x + half,
+        center.y - half,
+        thickness,
+        theme.mine,
+    );
+}
+
+/// Draws what a [Tile] shows inside its `interior`: its number, a flag or a mine.
+/// Wrong flags are crossed out and the detonated mine is ringed
+fn draw_glyph(tile: Tile, interior: Rect, theme: &Theme) {
+    let (center, size) = (interior.center(), interior.w);
+    match tile {
+        Tile::Hidden | Tile::Revealed => {}
+        Tile::Flag => draw_flag(center, size, theme),
+        Tile::WrongFlag => {
+            draw_flag(center, size, theme);
+            draw_cross(center, size, theme);
+        }
+        Tile::Number(local_mines) => draw_number(local_mines, center, size, theme),
+        Tile::Mine => draw_mine(center, size, theme),
+        Tile::DetonatedMine => {
+            draw_mine(center, size, theme);
+            draw_circle_lines(center.x, center.y, size * 0.45, size * 0.06, theme.glyph);
+        }
+    }
+}
+
+/// Draws `local_mines` centered on `center`, filling most of a cell `size` wide
+fn draw_number(local_mines: usize, center: Vec2, size: f32, theme: &Theme) {
+    theme.draw_centered_text(
+        &local_mines.to_string(),
+        center.x,
+        center.y,
+        size * 0.9,
+        theme.number_color(local_mines),
+    );
+}
+
+/// Draws a flag on a pole centered on `center`, scaled to a cell `size` wide
+fn draw_flag(center: Vec2, size: f32, theme: &Theme) {
+    let pole = center.x - size * 0.1;
+    let (top, bottom) = (center.y - size * 0.35, center.y + size * 0.3);
+    draw_line(pole, top, pole, bottom, size * 0.06, theme.glyph);
+    draw_rectangle(
+        center.x - size * 0.3,
+        bottom - size * 0.04,
+        size * 0.45,
+        size * 0.08,
+        theme.glyph,
+    );
+    draw_triangle(
+        Vec2::new(pole, top),
+        Vec2::new(pole, top + size * 0.3),
+        Vec2::new(center.x + size * 0.3, top + size * 0.15),
+        theme.pennant,
+    );
+}
+
+/// Draws a spiked mine centered on `center`, scaled to a cell `size` wide
+fn draw_mine(center: Vec2, size: f32, theme: &Theme) {
+    let thickness = size * 0.06;
+    let (straight, diagonal) = (size * 0.35, size * 0.25);
+    for (x, y) in [
+        (straight, 0.0),
+        (0.0, straight),
+        (diagonal, diagonal),
+        (diagonal, -diagonal),
+    ] {
+        draw_line(
+            center.x - x,
+            center.y - y,
+            center.x + x,
+            center.y + y,
+            thickness,
+            theme.glyph,
+        );
+    }
+    draw_circle(center.x, center.y, size * 0.25, theme.glyph);
+    draw_circle(
+        center.x - size * 0.08,
+        center.y - size * 0.08,
+        size * 0.06,
+        theme.revealed,
+    );
+}
+
+/// Returns the [Command] for the last key pressed. Arrows or WASD move the cursor, the rest act on the cell under it
+fn handle_keys<const W: usize, const H: usize>(game: &Game<W, H>) -> Option<Command> {
+    let (row_index, column_index) = game.cell_index();
+    let select = |row_index, column_index| {
+        return Some(Command::Select {
+            cell_index: (row_index, column_index),
+        });
+    };
+    let act = |action| {
+        return Some(Command::Act {
+            action,
+            cell_index: game.cell_index(),
+        });
+    };
+
+    return match get_last_key_pressed()? {
+        KeyCode::Up | KeyCode::W => select(row_index.saturating_sub(1), column_index),
+        KeyCode::Down | KeyCode::S => select((row_index + 1).min(H - 1), column_index),
+        KeyCode::Left | KeyCode::A => select(row_index, column_index.saturating_sub(1)),
+        KeyCode::Right | KeyCode::D => select(row_index, (column_index + 1).min(W - 1)),
+        KeyCode::Space => act(Action::Reveal),
+        KeyCode::F => match game.board()[game.cell_index()].is_flagged() {
+            true => act(Action::Unflag),
+            false => act(Action::Flag),
+        },
+        KeyCode::C => act(Action::Chord),
+        KeyCode::N => Some(Command::NewGame),
+        _ => None,
+    };
+}
+
+/// Returns the primary and secondary mouse buttons, which are left and right unless [Settings::swap_buttons] is on
+fn mouse_buttons(settings: Settings) -> (MouseButton, MouseButton) {
+    return match settings.swap_buttons {
+        true => (MouseButton::Right, MouseButton::Left),
+        false => (MouseButton::Left, MouseButton::Right),
+    };
+}
+
+/// Returns the [Command] for a [Press] released on the cell at `cell_index`. The primary button reveals and the secondary one flags, see [mouse_buttons]
+fn handle_click<const W: usize, const H: usize>(
+    game: &Game<W, H>,
+    cell_index: (usize, usize),
+    press: Press,
+) -> Option<Command> {
+    let cell = game.board().get_cell(cell_index)?;
+    let action = match press {
+        Press::Primary => Action::Reveal,
+        Press::Secondary if cell.is_flagged() => Action::Unflag,
+        Press::Secondary => Action::Flag,
+        Press::Both => Action::Chord,
+    };
+    return Some(Command::Act { action, cell_index });
+}
+
+/// Returns the cell under the mouse, or [None] when it is over the [hud] or off the board
+fn hovered_cell<const W: usize, const H: usize>(camera: Camera) -> Option<(usize, usize)> {
+    if hud::boundary().contains(mouse_position().into()) {
+        return None;
+    }
+    // the cells don't move, the pointer is moved back through the camera instead
+    let pointer = camera.to_board(mouse_position().into());
+    for (row_index, row) in cell_boundaries::<W, H>().into_iter().enumerate() {
+        for (column_index, boundary) in row.into_iter().enumerate() {
+            if boundary.contains(pointer) {
+                return Some((row_index, column_index));
+            }
+        }
+    }
+    return None;
+}
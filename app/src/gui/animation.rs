@@ -0,0 +1,160 @@
+//! Effects drawn over the board: revealed cells ripple out from the click, flags pop in, and a mine explodes before the others appear.
+//! They only change how cells look for a moment, the [Game](minesweeper::game::Game) has already moved on
+
+use std::f32::consts::PI;
+
+use super::Tile;
+
+/// Seconds between each cell of distance from the click in a reveal cascade
+const RIPPLE_STEP: f64 = 0.03;
+/// Seconds a revealed cell takes to grow in
+const APPEAR_TIME: f64 = 0.15;
+/// Seconds a flag takes to pop in
+const POP_TIME: f64 = 0.2;
+/// Seconds the explosion and shake last, before the other mines appear
+const EXPLOSION_TIME: f64 = 0.5;
+/// Seconds between each cell of distance from the explosion for the other mines
+const MINE_STEP: f64 = 0.05;
+
+/// How a cell comes into view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    /// grows from its center
+    Appear,
+    /// grows past its size and settles back
+    Pop,
+}
+impl Effect {
+    /// Seconds the effect lasts once it starts
+    fn duration(self) -> f64 {
+        return match self {
+            Effect::Appear => APPEAR_TIME,
+            Effect::Pop => POP_TIME,
+        };
+    }
+
+    /// The size of the cell at `progress` through the effect, as a fraction of its full size
+    pub fn scale(self, progress: f32) -> f32 {
+        return match self {
+            Effect::Appear => 1.0 - (1.0 - progress).powi(2),
+            Effect::Pop => progress + 0.3 * (PI * progress).sin(),
+        };
+    }
+}
+
+/// An [Effect] that starts at a time, in seconds from [macroquad::time::get_time]
+#[derive(Debug, Clone, Copy)]
+struct Scheduled {
+    effect: Effect,
+    start: f64,
+}
+
+/// The effects of the cells of one board. It finds what changed by comparing each frame's [Tile]s with the last frame's
+#[derive(Debug, Default)]
+pub struct Animations {
+    /// the last frame's tiles, row by row
+    tiles: Vec<Tile>,
+    width: usize,
+    /// the effect of each cell, row by row
+    cells: Vec<Option<Scheduled>>,
+    /// the detonated mine and when it went off
+    explosion: Option<((usize, usize), f64)>,
+}
+impl Animations {
+    /// Schedules effects for the `tiles` that changed since the last frame.
+    /// `tiles` is the board row by row, `width` cells wide, and `origin` is the cell the player last acted on
+    pub fn update(&mut self, tiles: Vec<Tile>, width: usize, origin: (usize, usize), now: f64) {
+        if tiles.len() != self.tiles.len() || width != self.width {
+            // a different board, there is nothing to compare with
+            self.cells = vec![None; tiles.len()];
+            self.explosion = None;
+            self.tiles = tiles;
+            self.width = width;
+            return;
+        }
+
+        let position = |index: usize| (index / width, index % width);
+        if !tiles.contains(&Tile::DetonatedMine) {
+            self.explosion = None;
+        }
+        if let Some(index) = tiles
+            .iter()
+            .zip(&self.tiles)
+            .position(|(&new, &old)| new == Tile::DetonatedMine && old != new)
+        {
+            self.explosion = Some((position(index), now));
+        }
+
+        for (index, (&new, &old)) in tiles.iter().zip(&self.tiles).enumerate() {
+            if new == old {
+                continue;
+            }
+            let cell_index = position(index);
+            let scheduled = |effect, start| Some(Scheduled { effect, start });
+            self.cells[index] = match (old, new) {
+                (_, Tile::Hidden) => None,
+                (Tile::Hidden, Tile::Flag) => scheduled(Effect::Pop, now),
+                (Tile::Hidden, Tile::Mine) => match self.explosion {
+                    Some((center, time)) => scheduled(
+                        Effect::Pop,
+                        time + EXPLOSION_TIME + distance(center, cell_index) * MINE_STEP,
+                    ),
+                    None => scheduled(
+                        Effect::Appear,
+                        now + distance(origin, cell_index) * RIPPLE_STEP,
+                    ),
+                },
+                (Tile::Hidden, Tile::Revealed | Tile::Number(_)) => scheduled(
+                    Effect::Appear,
+                    now + distance(origin, cell_index) * RIPPLE_STEP,
+                ),
+                _ => self.cells[index],
+            };
+        }
+        self.tiles = tiles;
+    }
+
+    /// Ends every effect, so the board is drawn as it is
+    pub fn skip(&mut self) {
+        self.cells.fill(None);
+        self.explosion = None;
+    }
+
+    /// Returns true while an effect hasn't finished
+    pub fn is_running(&self, now: f64) -> bool {
+        return self.explosion(now).is_some()
+            || self
+                .cells
+                .iter()
+                .flatten()
+                .any(|scheduled| scheduled.start + scheduled.effect.duration() > now);
+    }
+
+    /// Returns the [Effect] of the cell at `index`, row by row, and how far through it is, from 0 before it starts to 1.
+    /// Returns [None] once it is done
+    pub fn progress(&self, index: usize, now: f64) -> Option<(Effect, f32)> {
+        let scheduled = self.cells.get(index).copied().flatten()?;
+        let progress = (now - scheduled.start) / scheduled.effect.duration();
+        if progress >= 1.0 {
+            return None;
+        }
+        return Some((scheduled.effect, progress.max(0.0) as f32));
+    }
+
+    /// Returns the detonated mine and how far through the explosion is, from 0 to 1. Returns [None] once it is done
+    pub fn explosion(&self, now: f64) -> Option<((usize, usize), f32)> {
+        let (cell_index, time) = self.explosion?;
+        let progress = (now - time) / EXPLOSION_TIME;
+        if progress >= 1.0 {
+            return None;
+        }
+        return Some((cell_index, progress as f32));
+    }
+}
+
+/// The distance between the centers of two cells, in cells
+fn distance(a: (usize, usize), b: (usize, usize)) -> f64 {
+    let rows = a.0.abs_diff(b.0) as f64;
+    let columns = a.1.abs_diff(b.1) as f64;
+    return rows.hypot(columns);
+}
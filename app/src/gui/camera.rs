@@ -0,0 +1,199 @@
+//! Zooming and panning the board
+
+use macroquad::prelude::*;
+
+/// The most the board can be zoomed out, as a fraction of the size that fits the window
+const MIN_ZOOM: f32 = 0.5;
+/// The most the board can be zoomed in
+const MAX_ZOOM: f32 = 8.0;
+/// How much one step of the mouse wheel, or one press of + or -, zooms
+const ZOOM_STEP: f32 = 1.2;
+
+/// How the board is zoomed and panned. A point on the board that fits the window is drawn at `point * zoom + offset`.
+/// The default fits the board to the window
+#[derive(Debug, Clone, Copy)]
+pub struct Camera {
+    zoom: f32,
+    offset: Vec2,
+    /// where the pointer was last frame while dragging
+    drag: Option<Vec2>,
+    /// the selected cell when it was last followed, see [Camera::follow]
+    followed: Option<Rect>,
+}
+impl Default for Camera {
+    fn default() -> Self {
+        return Self {
+            zoom: 1.0,
+            offset: Vec2::ZERO,
+            drag: None,
+            followed: None,
+        };
+    }
+}
+impl Camera {
+    /// Zooms around the pointer with the mouse wheel, or around the center of `view` with + and -.
+    /// Pans while the middle button is held, or the left one after pressing it beside the board,
+    /// follows the `selected` cell when it moves and fits the board to the window when 0 is pressed.
+    /// `board` and `selected` are boundaries on the board that fits the window, the center of the window is kept over `board`.
+    /// `view` is the part of the window the board is seen in
+    pub fn update(&mut self, board: Rect, view: Rect, selected: Rect) {
+        if is_key_pressed(KeyCode::Key0) {
+            *self = Self::default();
+            return;
+        }
+
+        let pointer = Vec2::from(mouse_position());
+        let (_, wheel) = mouse_wheel();
+        if wheel != 0.0 {
+            // wheel units differ between platforms, so only the direction is used
+            self.zoom_at(pointer, ZOOM_STEP.powf(wheel.signum()));
+        }
+        if is_key_pressed(KeyCode::Equal) || is_key_pressed(KeyCode::KpAdd) {
+            self.zoom_at(view.center(), ZOOM_STEP);
+        }
+        if is_key_pressed(KeyCode::Minus) || is_key_pressed(KeyCode::KpSubtract) {
+            self.zoom_at(view.center(), 1.0 / ZOOM_STEP);
+        }
+
+        // a left press on the board acts on a cell, beside it there is nothing else to do
+        let is_beside_board = view.contains(pointer) && !board.contains(self.to_board(pointer));
+        self.drag = match self.drag {
+            Some(previous)
+                if is_mouse_button_down(MouseButton::Middle)
+                    || is_mouse_button_down(MouseButton::Left) =>
+            {
+                self.offset += pointer - previous;
+                Some(pointer)
+            }
+            _ if is_mouse_button_pressed(MouseButton::Middle) => Some(pointer),
+            _ if is_mouse_button_pressed(MouseButton::Left) && is_beside_board => Some(pointer),
+            _ => None,
+        };
+
+        if self.followed != Some(selected) {
+            self.follow(selected, view);
+        }
+        self.keep_over(board);
+    }
+
+    /// Pans as little as it takes to show `cell`, a boundary on the board that fits the window, in `view`
+    pub fn follow(&mut self, cell: Rect, view: Rect) {
+        let shown = self.apply(cell);
+        self.offset += Vec2::new(
+            shift_into(shown.x, shown.right(), view.x, view.right()),
+            shift_into(shown.y, shown.bottom(), view.y, view.bottom()),
+        );
+        self.followed = Some(cell);
+    }
+
+    /// Zooms by `factor`, keeping the board under `point` in place
+    pub fn zoom_at(&mut self, point: Vec2, factor: f32) {
+        let zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+        let factor = zoom / self.zoom;
+        self.offset = point - (point - self.offset) * factor;
+        self.zoom = zoom;
+    }
+
+    /// Pans so the center of the window is over `board`
+    fn keep_over(&mut self, board: Rect) {
+        let center = Vec2::new(screen_width(), screen_height()) / 2.0;
+        let (top_left, bottom_right) = (
+            self.to_screen(board.point()),
+            self.to_screen(board.point() + board.size()),
+        );
+        self.offset += center - center.clamp(top_left, bottom_right);
+    }
+
+    /// How many times bigger than the board that fits the window the board is drawn
+    pub fn zoom(&self) -> f32 {
+        return self.zoom;
+    }
+
+    /// Where a `point` on the board that fits the window is drawn
+    pub fn to_screen(self, point: Vec2) -> Vec2 {
+        return point * self.zoom + self.offset;
+    }
+
+    /// The point on the board that fits the window drawn at `point`. Undoes [Camera::to_screen]
+    pub fn to_board(self, point: Vec2) -> Vec2 {
+        return (point - self.offset) / self.zoom;
+    }
+
+    /// Where a `rect` on the board that fits the window is drawn
+    pub fn apply(&self, rect: Rect) -> Rect {
+        let point = self.to_screen(rect.point());
+        return Rect::new(point.x, point.y, rect.w * self.zoom, rect.h * self.zoom);
+    }
+}
+
+/// How far to move from `start` to `end` to be between `view_start` and `view_end`. The start wins when it doesn't fit
+fn shift_into(start: f32, end: f32, view_start: f32, view_end: f32) -> f32 {
+    if start < view_start {
+        return view_start - start;
+    }
+    if end > view_end {
+        return (view_end - end).max(view_start - start);
+    }
+    return 0.0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera(zoom: f32, offset: Vec2) -> Camera {
+        return Camera {
+            zoom,
+            offset,
+            ..Camera::default()
+        };
+    }
+
+    #[test]
+    fn to_board_undoes_to_screen() {
+        let camera = camera(2.5, Vec2::new(-40.0, 12.0));
+        for point in [Vec2::ZERO, Vec2::new(3.0, 7.0), Vec2::new(-120.5, 64.25)] {
+            assert!(camera
+                .to_board(camera.to_screen(point))
+                .abs_diff_eq(point, 1e-4));
+        }
+    }
+
+    #[test]
+    fn zooming_keeps_the_point_under_the_cursor() {
+        let mut camera = camera(1.5, Vec2::new(10.0, -20.0));
+        let cursor = Vec2::new(300.0, 200.0);
+        let under_cursor = camera.to_board(cursor);
+        for factor in [ZOOM_STEP, ZOOM_STEP, 1.0 / ZOOM_STEP] {
+            camera.zoom_at(cursor, factor);
+            assert!(camera.to_screen(under_cursor).abs_diff_eq(cursor, 1e-3));
+        }
+    }
+
+    #[test]
+    fn zoom_is_clamped() {
+        let mut camera = Camera::default();
+        camera.zoom_at(Vec2::ZERO, 1000.0);
+        assert_eq!(camera.zoom(), MAX_ZOOM);
+        camera.zoom_at(Vec2::ZERO, 0.0001);
+        assert_eq!(camera.zoom(), MIN_ZOOM);
+    }
+
+    #[test]
+    fn following_pans_only_as_far_as_needed() {
+        let view = Rect::new(0.0, 50.0, 400.0, 300.0);
+        let mut camera = camera(2.0, Vec2::ZERO);
+
+        // already in view
+        camera.follow(Rect::new(10.0, 30.0, 10.0, 10.0), view);
+        assert_eq!(camera.offset, Vec2::ZERO);
+
+        // off the bottom right, shown at (400, 400) to (420, 420)
+        camera.follow(Rect::new(200.0, 200.0, 10.0, 10.0), view);
+        assert_eq!(camera.offset, Vec2::new(-20.0, -70.0));
+
+        // off the top left
+        camera.follow(Rect::new(0.0, 0.0, 10.0, 10.0), view);
+        assert_eq!(camera.offset, Vec2::new(0.0, 50.0));
+    }
+}
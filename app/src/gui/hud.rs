@@ -0,0 +1,262 @@
+//! The bar above the board: the remaining mines, a face button that starts a new game, and the time
+
+use std::time::Duration;
+
+use macroquad::prelude::*;
+
+use super::{draw_bevel, Theme};
+use minesweeper::game::GameState;
+
+/// The fraction of the window's height the bar takes
+const HUD_FILL: f32 = 0.1;
+
+/// The lit segments of each digit from 0 to 9, one bit per segment from a (top) to g (middle)
+const DIGITS: [u8; 10] = [
+    0b0111111, 0b0000110, 0b1011011, 0b1001111, 0b1100110, 0b1101101, 0b1111101, 0b0000111,
+    0b1111111, 0b1101111,
+];
+/// The lit segment of a minus sign
+const MINUS: u8 = 0b1000000;
+
+/// The expression of the face button
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Face {
+    Smile,
+    /// a cell is being pressed
+    Surprised,
+    /// the game was won
+    Cool,
+    /// the game was lost
+    Dead,
+}
+impl Face {
+    /// The face for a game in `state`, surprised while `is_pressing` a cell on the board
+    pub fn of(state: GameState, is_pressing: bool) -> Self {
+        return match state {
+            GameState::Won => Face::Cool,
+            GameState::GameOver => Face::Dead,
+            _ if is_pressing => Face::Surprised,
+            _ => Face::Smile,
+        };
+    }
+}
+
+/// The bar across the top of the window. The board is laid out below it
+pub fn boundary() -> Rect {
+    return Rect::new(0.0, 0.0, screen_width(), screen_height() * HUD_FILL);
+}
+
+/// The face button in the middle of the bar
+fn face_boundary() -> Rect {
+    let bar = boundary();
+    let size = bar.h * 0.8;
+    return Rect::new(
+        bar.center().x - size / 2.0,
+        bar.center().y - size / 2.0,
+        size,
+        size,
+    );
+}
+
+/// Returns true when the face button was clicked with the `primary` mouse button this frame
+pub fn is_face_clicked(primary: MouseButton) -> bool {
+    return is_mouse_button_released(primary) && face_boundary().contains(mouse_position().into());
+}
+
+/// Draws the bar with `remaining_mines` on the left, the `face` button in the middle, and `elapsed` on the right.
+/// The button is pressed in while the `primary` mouse button is held on it
+pub fn draw(
+    remaining_mines: isize,
+    elapsed: Duration,
+    face: Face,
+    primary: MouseButton,
+    theme: &Theme,
+) {
+    let bar = boundary();
+    draw_rectangle(bar.x, bar.y, bar.w, bar.h, theme.hidden);
+    draw_line(
+        bar.left(),
+        bar.bottom(),
+        bar.right(),
+        bar.bottom(),
+        bar.h * 0.04,
+        theme.border,
+    );
+
+    let counter_height = bar.h * 0.7;
+    let counter_width = counter_height * 0.55 * 3.0;
+    let margin = (bar.h - counter_height) / 2.0;
+    let mines = match remaining_mines.clamp(-99, 999) {
+        mines if mines < 0 => format!("-{:02}", -mines),
+        mines => format!("{:03}", mines),
+    };
+    draw_counter(
+        &mines,
+        Rect::new(margin, margin, counter_width, counter_height),
+        theme,
+    );
+    let seconds = format!("{:03}", elapsed.as_secs().min(999));
+    draw_counter(
+        &seconds,
+        Rect::new(
+            bar.right() - margin - counter_width,
+            margin,
+            counter_width,
+            counter_height,
+        ),
+        theme,
+    );
+
+    draw_face(face, primary, theme);
+}
+
+/// Draws `text`, made of digits and minus signs, as seven-segment digits filling `boundary`
+fn draw_counter(text: &str, boundary: Rect, theme: &Theme) {
+    draw_rectangle(boundary.x, boundary.y, boundary.w, boundary.h, BLACK);
+
+    let digit_width = boundary.w / text.chars().count() as f32;
+    for (index, character) in text.chars().enumerate() {
+        let lit = match character.to_digit(10) {
+            Some(digit) => DIGITS[digit as usize],
+            None => MINUS,
+        };
+        let digit = Rect::new(
+            boundary.x + digit_width * index as f32,
+            boundary.y,
+            digit_width,
+            boundary.h,
+        );
+        draw_digit(lit, digit, theme.counter);
+    }
+}
+
+/// Draws the `lit` segments of a seven-segment digit inside `boundary` in `color`, and the others faintly
+fn draw_digit(lit: u8, boundary: Rect, color: Color) {
+    let padding = boundary.w * 0.12;
+    let (x, y) = (boundary.x + padding, boundary.y + padding);
+    let (w, h) = (boundary.w - 2.0 * padding, boundary.h - 2.0 * padding);
+    let t = w * 0.2;
+    let middle = y + h / 2.0 - t / 2.0;
+    let upper = middle - y - t;
+    let lower = y + h - t - (middle + t);
+
+    // a, b, c, d, e, f, g
+    let segments = [
+        Rect::new(x + t, y, w - 2.0 * t, t),
+        Rect::new(x + w - t, y + t, t, upper),
+        Rect::new(x + w - t, middle + t, t, lower),
+        Rect::new(x + t, y + h - t, w - 2.0 * t, t),
+        Rect::new(x, middle + t, t, lower),
+        Rect::new(x, y + t, t, upper),
+        Rect::new(x + t, middle, w - 2.0 * t, t),
+    ];
+    for (index, segment) in segments.into_iter().enumerate() {
+        let color = match lit & (1 << index) {
+            0 => Color { a: 0.15, ..color },
+            _ => color,
+        };
+        draw_rectangle(segment.x, segment.y, segment.w, segment.h, color);
+    }
+}
+
+/// Draws the face button with `face` on it, pressed in while the `primary` mouse button is held on it
+fn draw_face(face: Face, primary: MouseButton, theme: &Theme) {
+    let button = face_boundary();
+    let is_pressed = is_mouse_button_down(primary) && button.contains(mouse_position().into());
+    draw_rectangle(button.x, button.y, button.w, button.h, theme.border);
+    let interior = Rect::new(
+        button.x + button.w * 0.05,
+        button.y + button.h * 0.05,
+        button.w * 0.9,
+        button.h * 0.9,
+    );
+    match is_pressed {
+        true => draw_rectangle(
+            interior.x,
+            interior.y,
+            interior.w,
+            interior.h,
+            theme.revealed,
+        ),
+        false => {
+            draw_rectangle(interior.x, interior.y, interior.w, interior.h, theme.hidden);
+            draw_bevel(interior, theme.hidden);
+        }
+    }
+
+    let center = interior.center();
+    let radius = interior.w * 0.35;
+    let thickness = radius * 0.1;
+    draw_circle(center.x, center.y, radius, YELLOW);
+    draw_circle_lines(center.x, center.y, radius, thickness, BLACK);
+
+    let (eye_x, eye_y) = (radius * 0.35, center.y - radius * 0.25);
+    match face {
+        Face::Smile | Face::Surprised => {
+            draw_circle(center.x - eye_x, eye_y, radius * 0.1, BLACK);
+            draw_circle(center.x + eye_x, eye_y, radius * 0.1, BLACK);
+        }
+        Face::Cool => {
+            let lens = Vec2::new(radius * 0.4, radius * 0.25);
+            for side in [-1.0, 1.0] {
+                let x = center.x + side * eye_x - lens.x / 2.0;
+                draw_rectangle(x, eye_y - lens.y / 2.0, lens.x, lens.y, BLACK);
+            }
+            draw_line(
+                center.x - radius * 0.85,
+                eye_y,
+                center.x + radius * 0.85,
+                eye_y,
+                thickness,
+                BLACK,
+            );
+        }
+        Face::Dead => {
+            let size = radius * 0.12;
+            for x in [center.x - eye_x, center.x + eye_x] {
+                draw_line(
+                    x - size,
+                    eye_y - size,
+                    x + size,
+                    eye_y + size,
+                    thickness,
+                    BLACK,
+                );
+                draw_line(
+                    x - size,
+                    eye_y + size,
+                    x + size,
+                    eye_y - size,
+                    thickness,
+                    BLACK,
+                );
+            }
+        }
+    }
+
+    let mouth_y = center.y + radius * 0.3;
+    match face {
+        Face::Surprised => {
+            draw_circle_lines(center.x, mouth_y, radius * 0.18, thickness, BLACK);
+        }
+        // a smile curves up at the ends, a frown down
+        Face::Smile | Face::Cool | Face::Dead => {
+            let curve = match face {
+                Face::Dead => -radius * 0.15,
+                _ => radius * 0.15,
+            };
+            let points: Vec<Vec2> = (0..=8)
+                .map(|step| {
+                    let t = step as f32 / 8.0 * 2.0 - 1.0;
+                    Vec2::new(
+                        center.x + t * radius * 0.45,
+                        mouth_y + curve * (1.0 - t * t) - curve / 2.0,
+                    )
+                })
+                .collect();
+            for pair in points.windows(2) {
+                draw_line(pair[0].x, pair[0].y, pair[1].x, pair[1].y, thickness, BLACK);
+            }
+        }
+    }
+}
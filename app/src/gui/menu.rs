@@ -0,0 +1,251 @@
+//! The pages around the board: buttons, settings and statistics
+
+use std::time::Duration;
+
+use macroquad::prelude::*;
+
+use super::Theme;
+use minesweeper::game::{Difficulty, GameState};
+
+/// What the player can change on the settings page
+#[derive(Debug, Clone, Copy)]
+pub struct Settings {
+    /// outline the cell the keyboard acts on
+    pub show_cursor: bool,
+    /// reveal with the right mouse button and flag with the left
+    pub swap_buttons: bool,
+    /// the index of the [Theme] in use
+    pub theme: usize,
+    /// play the reveal, flag and explosion animations
+    pub animations: bool,
+    /// the sound volume, as a percentage
+    pub volume: u8,
+    pub mute: bool,
+}
+impl Default for Settings {
+    fn default() -> Self {
+        return Self {
+            show_cursor: true,
+            swap_buttons: false,
+            theme: 0,
+            animations: true,
+            volume: 75,
+            mute: false,
+        };
+    }
+}
+
+/// The results of the games finished at one [Difficulty] this session
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Record {
+    pub played: usize,
+    pub won: usize,
+    /// the quickest win
+    pub best: Option<Duration>,
+}
+impl Record {
+    /// Counts a game that finished in `state` after `elapsed`
+    pub fn add(&mut self, state: GameState, elapsed: Duration) {
+        self.played += 1;
+        if state.is_won() {
+            self.won += 1;
+            self.best = Some(self.best.map_or(elapsed, |best| best.min(elapsed)));
+        }
+    }
+}
+
+/// A [Record] for each [Difficulty]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Statistics {
+    /// in the order of [Difficulty::ALL]
+    records: [Record; Difficulty::ALL.len()],
+}
+impl Statistics {
+    pub fn record(&self, difficulty: Difficulty) -> &Record {
+        return &self.records[position(difficulty)];
+    }
+    pub fn record_mut(&mut self, difficulty: Difficulty) -> &mut Record {
+        return &mut self.records[position(difficulty)];
+    }
+}
+
+/// The position of `difficulty` in [Difficulty::ALL]
+fn position(difficulty: Difficulty) -> usize {
+    return Difficulty::ALL
+        .iter()
+        .position(|&other| other == difficulty)
+        .unwrap_or_default();
+}
+
+/// A button in a [Menu]
+#[derive(Debug, Clone)]
+pub struct Button {
+    label: String,
+    enabled: bool,
+}
+impl Button {
+    pub fn new(label: impl Into<String>) -> Self {
+        return Self {
+            label: label.into(),
+            enabled: true,
+        };
+    }
+    /// A disabled button is drawn greyed out and can't be picked
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        return self;
+    }
+}
+
+/// [Button]s in a column or a row, picked by clicking, or by moving the focus with the arrow keys or WASD and pressing Enter or Space
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Menu {
+    /// the index of the focused button
+    focus: usize,
+}
+impl Menu {
+    /// Focuses the first button, for when a different page opens
+    pub fn reset(&mut self) {
+        self.focus = 0;
+    }
+
+    /// Draws the `title`, `lines` of text and `buttons` in a column under them.
+    /// Returns the index of the button picked this frame
+    pub fn show(
+        &mut self,
+        theme: &Theme,
+        title: &str,
+        lines: &[String],
+        buttons: &[Button],
+    ) -> Option<usize> {
+        let unit = screen_height() / 16.0;
+        let center = screen_width() / 2.0;
+
+        theme.draw_centered_text(title, center, unit * 2.5, unit * 2.0, theme.text);
+        let mut y = unit * 4.0;
+        for line in lines {
+            theme.draw_centered_text(line, center, y, unit * 0.7, theme.text);
+            y += unit;
+        }
+
+        let (width, height) = ((screen_width() * 0.4).max(unit * 8.0), unit * 1.2);
+        y += unit * 0.5;
+        let boundaries: Vec<Rect> = (0..buttons.len())
+            .map(|index| {
+                let top = y + index as f32 * (height + unit * 0.3);
+                return Rect::new(center - width / 2.0, top, width, height);
+            })
+            .collect();
+        return self.pick(
+            theme,
+            buttons,
+            &boundaries,
+            (KeyCode::Up, KeyCode::W),
+            (KeyCode::Down, KeyCode::S),
+        );
+    }
+
+    /// Draws `buttons` side by side, centered on `y`, with the focus moved by left/right or AD.
+    /// Returns the index of the button picked this frame
+    pub fn show_row(&mut self, theme: &Theme, y: f32, buttons: &[Button]) -> Option<usize> {
+        let unit = screen_height() / 16.0;
+        let (width, height, gap) = (unit * 5.0, unit, unit * 0.5);
+        let left = (screen_width() - buttons.len() as f32 * (width + gap) + gap) / 2.0;
+        let boundaries: Vec<Rect> = (0..buttons.len())
+            .map(|index| {
+                let x = left + index as f32 * (width + gap);
+                return Rect::new(x, y - height / 2.0, width, height);
+            })
+            .collect();
+        return self.pick(
+            theme,
+            buttons,
+            &boundaries,
+            (KeyCode::Left, KeyCode::A),
+            (KeyCode::Right, KeyCode::D),
+        );
+    }
+
+    /// Draws each button in its boundary and returns the index of the one picked this frame.
+    /// Either key of `previous` or `next` moves the focus
+    fn pick(
+        &mut self,
+        theme: &Theme,
+        buttons: &[Button],
+        boundaries: &[Rect],
+        previous: (KeyCode, KeyCode),
+        next: (KeyCode, KeyCode),
+    ) -> Option<usize> {
+        let unit = screen_height() / 16.0;
+
+        // skip disabled buttons, like one that was disabled while focused
+        self.move_focus(buttons, 0);
+        let mut picked = None;
+        if is_key_pressed(previous.0) || is_key_pressed(previous.1) {
+            self.move_focus(buttons, -1);
+        }
+        if is_key_pressed(next.0) || is_key_pressed(next.1) {
+            self.move_focus(buttons, 1);
+        }
+        if is_key_pressed(KeyCode::Enter)
+            || is_key_pressed(KeyCode::KpEnter)
+            || is_key_pressed(KeyCode::Space)
+        {
+            picked = Some(self.focus);
+        }
+
+        let mouse = Vec2::from(mouse_position());
+        let mouse_moved = mouse_delta_position() != Vec2::ZERO;
+        for (index, (button, boundary)) in buttons.iter().zip(boundaries).enumerate() {
+            if button.enabled && boundary.contains(mouse) {
+                if mouse_moved {
+                    self.focus = index;
+                }
+                if is_mouse_button_released(MouseButton::Left) {
+                    picked = Some(index);
+                }
+            }
+
+            // disabled buttons are faded out
+            let alpha = if button.enabled { 1.0 } else { 0.4 };
+            let fade = |color: Color| Color { a: alpha, ..color };
+            let fill = match index == self.focus && button.enabled {
+                true => theme.revealed,
+                false => theme.hidden,
+            };
+            draw_rectangle(boundary.x, boundary.y, boundary.w, boundary.h, fade(fill));
+            draw_rectangle_lines(
+                boundary.x,
+                boundary.y,
+                boundary.w,
+                boundary.h,
+                2.0,
+                theme.border,
+            );
+            let center = boundary.center();
+            theme.draw_centered_text(
+                &button.label,
+                center.x,
+                center.y,
+                unit * 0.7,
+                fade(theme.text),
+            );
+        }
+
+        return picked.filter(|&index| buttons.get(index).is_some_and(|button| button.enabled));
+    }
+
+    /// Moves the focus by `step` buttons, then on in the same direction until an enabled button
+    fn move_focus(&mut self, buttons: &[Button], step: isize) {
+        if buttons.iter().all(|button| !button.enabled) {
+            return;
+        }
+        let length = buttons.len() as isize;
+        let direction = if step < 0 { -1 } else { 1 };
+        let mut focus = (self.focus as isize + step).rem_euclid(length);
+        while !buttons[focus as usize].enabled {
+            focus = (focus + direction).rem_euclid(length);
+        }
+        self.focus = focus as usize;
+    }
+}
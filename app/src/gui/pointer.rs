@@ -0,0 +1,70 @@
+//! Clicks on cells that act when the button is released, like a button does
+
+use macroquad::prelude::*;
+
+/// The buttons a cell is pressed with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Press {
+    /// reveals
+    Primary,
+    /// flags or unflags
+    Secondary,
+    /// both at once, chords
+    Both,
+}
+
+/// The cell under the mouse and the press on it. A press acts when released over the cell it started on,
+/// so moving off the cell before releasing cancels it
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Pointer {
+    hovered: Option<(usize, usize)>,
+    /// the cell the press started on
+    press: Option<((usize, usize), Press)>,
+}
+impl Pointer {
+    /// Follows the mouse for a frame, now over the cell at `hovered`.
+    /// Returns the cell and the [Press] when the press is released over the cell it started on
+    pub fn update(
+        &mut self,
+        hovered: Option<(usize, usize)>,
+        primary: MouseButton,
+        secondary: MouseButton,
+    ) -> Option<((usize, usize), Press)> {
+        self.hovered = hovered;
+
+        // a quick click can be pressed and released in the same frame
+        let is_held = |button| is_mouse_button_down(button) || is_mouse_button_pressed(button);
+        if is_mouse_button_pressed(primary) || is_mouse_button_pressed(secondary) {
+            let press = match (is_held(primary), is_held(secondary)) {
+                (true, true) => Press::Both,
+                (true, false) => Press::Primary,
+                (false, _) => Press::Secondary,
+            };
+            self.press = hovered.map(|cell_index| (cell_index, press));
+        }
+
+        if is_mouse_button_released(primary) || is_mouse_button_released(secondary) {
+            // the first release ends a press with both buttons, the other one does nothing
+            return self
+                .press
+                .take()
+                .filter(|&(cell_index, _)| hovered == Some(cell_index));
+        }
+        if !is_mouse_button_down(primary) && !is_mouse_button_down(secondary) {
+            self.press = None;
+        }
+        return None;
+    }
+
+    /// The cell under the mouse, if it is over the board
+    pub fn hovered(&self) -> Option<(usize, usize)> {
+        return self.hovered;
+    }
+
+    /// The pressed cell and how, while the mouse is still over it
+    pub fn pressed(&self) -> Option<((usize, usize), Press)> {
+        return self
+            .press
+            .filter(|&(cell_index, _)| self.hovered == Some(cell_index));
+    }
+}
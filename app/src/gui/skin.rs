@@ -0,0 +1,179 @@
+//! Cells drawn from a sprite sheet instead of rectangles
+//!
+//! A skin is a manifest in the [config file](crate::config) format naming an image and where each tile is in it, in pixels:
+//! ```text
+//! image = windows.png
+//!
+//! [tiles]
+//! hidden = 0, 0, 16, 16
+//! flag = 16, 0, 16, 16
+//! 1 = 0, 16, 16, 16
+//! ```
+//! Every [Tile] must be listed. The image path is relative to the manifest.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use macroquad::prelude::*;
+
+use crate::config::Config;
+use minesweeper::cell::{Cell, Outcome};
+
+/// One picture in a [Skin]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tile {
+    Hidden,
+    Flag,
+    /// a flag on a safe cell, shown when the game is finished
+    WrongFlag,
+    /// a revealed cell without neighboring mines
+    Revealed,
+    /// a revealed cell with 1 to 8 neighboring mines
+    Number(usize),
+    Mine,
+    /// the mine that ended the game
+    DetonatedMine,
+}
+impl Tile {
+    /// Every tile a manifest has to list
+    pub const ALL: [Tile; 14] = [
+        Tile::Hidden,
+        Tile::Flag,
+        Tile::WrongFlag,
+        Tile::Revealed,
+        Tile::Number(1),
+        Tile::Number(2),
+        Tile::Number(3),
+        Tile::Number(4),
+        Tile::Number(5),
+        Tile::Number(6),
+        Tile::Number(7),
+        Tile::Number(8),
+        Tile::Mine,
+        Tile::DetonatedMine,
+    ];
+
+    /// The key of this tile in the `[tiles]` section of a manifest
+    pub fn key(self) -> String {
+        return match self {
+            Tile::Hidden => String::from("hidden"),
+            Tile::Flag => String::from("flag"),
+            Tile::WrongFlag => String::from("wrong_flag"),
+            Tile::Revealed => String::from("revealed"),
+            Tile::Number(local_mines) => local_mines.to_string(),
+            Tile::Mine => String::from("mine"),
+            Tile::DetonatedMine => String::from("detonated_mine"),
+        };
+    }
+
+    /// The tile a [Cell] of a game in progress shows
+    pub fn of_cell(cell: &Cell) -> Self {
+        return match (cell.is_revealed(), cell.is_flagged(), cell.is_mine()) {
+            (false, true, _) => Tile::Flag,
+            (false, _, _) => Tile::Hidden,
+            (true, _, true) => Tile::Mine,
+            (true, _, false) if cell.local_mines() > 0 => Tile::Number(cell.local_mines()),
+            (true, _, false) => Tile::Revealed,
+        };
+    }
+
+    /// The tile a cell of a finished game shows
+    pub fn of_outcome(outcome: Outcome) -> Self {
+        return match outcome {
+            Outcome::CorrectFlag => Tile::Flag,
+            Outcome::WrongFlag => Tile::WrongFlag,
+            Outcome::MissedMine => Tile::Mine,
+            Outcome::DetonatedMine => Tile::DetonatedMine,
+            Outcome::Safe { local_mines: 0 } => Tile::Revealed,
+            Outcome::Safe { local_mines } => Tile::Number(local_mines),
+        };
+    }
+}
+
+/// A sprite sheet with the place of every [Tile] in it
+#[derive(Debug, Clone)]
+pub struct Skin {
+    /// the manifest this skin was loaded from
+    pub path: PathBuf,
+    texture: Texture2D,
+    tiles: HashMap<Tile, Rect>,
+}
+impl Skin {
+    /// Loads the skin described by the manifest at `path`
+    /// # Errors
+    /// - When the manifest or its image can't be read or parsed
+    /// - When a [Tile] is missing, or isn't inside the image
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let manifest = std::fs::read_to_string(path)?.parse::<Config>()?;
+
+        let image_path = manifest.get("", "image").ok_or("there is no image key")?;
+        let image_path = path
+            .parent()
+            .unwrap_or(Path::new(""))
+            .join(image_path.trim());
+        let bytes = std::fs::read(&image_path)
+            .map_err(|error| format!("{}: {}", image_path.display(), error))?;
+        let image = Image::from_file_with_format(&bytes, None)
+            .map_err(|error| format!("{}: {}", image_path.display(), error))?;
+        let bounds = Rect::new(0.0, 0.0, image.width as f32, image.height as f32);
+
+        let mut tiles = HashMap::new();
+        for tile in Tile::ALL {
+            let key = tile.key();
+            let value = manifest
+                .get("tiles", &key)
+                .ok_or(format!("tile {} is missing", key))?;
+            let source = parse_rect(value).ok_or(format!(
+                "tile {}: {} is not x, y, width, height",
+                key, value
+            ))?;
+            if source.w == 0.0
+                || source.h == 0.0
+                || source.right() > bounds.right()
+                || source.bottom() > bounds.bottom()
+            {
+                return Err(format!("tile {} is not inside the image", key).into());
+            }
+            tiles.insert(tile, source);
+        }
+
+        let texture = Texture2D::from_image(&image);
+        // keep pixel art sharp when it is scaled up
+        texture.set_filter(FilterMode::Nearest);
+
+        return Ok(Self {
+            path: path.to_path_buf(),
+            texture,
+            tiles,
+        });
+    }
+
+    /// Draws `tile` stretched over `boundary`
+    pub fn draw(&self, tile: Tile, boundary: Rect) {
+        draw_texture_ex(
+            &self.texture,
+            boundary.x,
+            boundary.y,
+            WHITE,
+            DrawTextureParams {
+                dest_size: Some(boundary.size()),
+                source: self.tiles.get(&tile).copied(),
+                ..Default::default()
+            },
+        );
+    }
+}
+
+/// Parses a rectangle of whole pixels written `x, y, width, height`
+fn parse_rect(text: &str) -> Option<Rect> {
+    let numbers = text
+        .split(',')
+        .map(|number| number.trim().parse::<u16>().ok())
+        .collect::<Option<Vec<u16>>>()?;
+    return match numbers[..] {
+        [x, y, w, h] => Some(Rect::new(x as f32, y as f32, w as f32, h as f32)),
+        _ => None,
+    };
+}
@@ -0,0 +1,180 @@
+//! Sound effects for the [Event]s of a game
+
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    f32::consts::TAU,
+    path::Path,
+};
+
+use macroquad::audio::{load_sound_from_bytes, play_sound, PlaySoundParams, Sound};
+
+use minesweeper::game::Event;
+
+/// The directory sounds are loaded from when the config file doesn't name one
+pub const DEFAULT_DIRECTORY: &str = "assets/sounds";
+/// Samples per second of the [built-in](built_in) sounds
+const SAMPLE_RATE: u32 = 22050;
+
+/// A sound for each [Event] that has one
+#[derive(Default, Clone)]
+pub struct Sounds {
+    sounds: HashMap<Event, Sound>,
+}
+impl std::fmt::Debug for Sounds {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // macroquad's Sound isn't Debug
+        return f
+            .debug_struct("Sounds")
+            .field("events", &self.sounds.keys().collect::<Vec<_>>())
+            .finish();
+    }
+}
+impl Sounds {
+    /// Loads `EVENT.wav` or `EVENT.ogg` from `directory` for each [Event], like `explosion.ogg`.
+    /// Events without a file play a [built-in](built_in) tone, and files that can't be used are skipped with a warning.
+    /// Nothing is loaded without the `audio` feature
+    pub async fn load(directory: &Path) -> Self {
+        let mut sounds = HashMap::new();
+        if !cfg!(feature = "audio") {
+            return Self { sounds };
+        }
+
+        for event in Event::ALL {
+            for extension in ["wav", "ogg"] {
+                let path = directory.join(format!("{}.{}", event, extension));
+                let bytes = match std::fs::read(&path) {
+                    Ok(bytes) => bytes,
+                    Err(error) if error.kind() == std::io::ErrorKind::NotFound => continue,
+                    Err(error) => {
+                        eprintln!("warning: {}: {}", path.display(), error);
+                        continue;
+                    }
+                };
+                // the decoder panics on anything else
+                if !is_wav(&bytes) && !bytes.starts_with(b"OggS") {
+                    eprintln!("warning: {} is not a WAV or Ogg file", path.display());
+                    continue;
+                }
+                match load_sound_from_bytes(&bytes).await {
+                    Ok(sound) => {
+                        sounds.insert(event, sound);
+                        break;
+                    }
+                    Err(error) => eprintln!("warning: {}: {}", path.display(), error),
+                }
+            }
+            if let Entry::Vacant(entry) = sounds.entry(event) {
+                match load_sound_from_bytes(&built_in(event)).await {
+                    Ok(sound) => {
+                        entry.insert(sound);
+                    }
+                    Err(error) => eprintln!("warning: built-in {} sound: {}", event, error),
+                }
+            }
+        }
+
+        return Self { sounds };
+    }
+
+    /// Plays the sound of `event`, if it has one, at `volume` from 0 to 1
+    pub fn play(&self, event: Event, volume: f32) {
+        if let Some(sound) = self.sounds.get(&event) {
+            play_sound(
+                sound,
+                PlaySoundParams {
+                    looped: false,
+                    volume,
+                },
+            );
+        }
+    }
+}
+
+/// Returns true when `bytes` start with a WAV header
+fn is_wav(bytes: &[u8]) -> bool {
+    return bytes.len() >= 12 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WAVE";
+}
+
+/// A short WAV of tones for `event`, used when there is no file for it
+fn built_in(event: Event) -> Vec<u8> {
+    // the frequency in Hz and length in seconds of each note, with no frequency for noise
+    let notes: &[(Option<f32>, f32)] = match event {
+        Event::Reveal => &[(Some(880.0), 0.05)],
+        Event::Cascade => &[
+            (Some(660.0), 0.04),
+            (Some(880.0), 0.04),
+            (Some(1100.0), 0.08),
+        ],
+        Event::Flag => &[(Some(520.0), 0.08)],
+        Event::Unflag => &[(Some(390.0), 0.08)],
+        Event::Chord => &[(Some(740.0), 0.05), (Some(990.0), 0.07)],
+        Event::Explosion => &[(None, 0.6)],
+        Event::Victory => &[
+            (Some(523.0), 0.12),
+            (Some(659.0), 0.12),
+            (Some(784.0), 0.12),
+            (Some(1047.0), 0.3),
+        ],
+    };
+
+    let mut samples = Vec::new();
+    // a xorshift generator, so the noise is the same every time
+    let mut noise = 0x2545_f491_u32;
+    for &(frequency, length) in notes {
+        let count = (SAMPLE_RATE as f32 * length) as usize;
+        for index in 0..count {
+            let wave = match frequency {
+                Some(frequency) => (TAU * frequency * index as f32 / SAMPLE_RATE as f32).sin(),
+                None => {
+                    noise ^= noise << 13;
+                    noise ^= noise >> 17;
+                    noise ^= noise << 5;
+                    noise as f32 / u32::MAX as f32 * 2.0 - 1.0
+                }
+            };
+            // each note fades out, so it doesn't click when it stops
+            let envelope = 1.0 - index as f32 / count as f32;
+            samples.push((wave * envelope * 0.4 * i16::MAX as f32) as i16);
+        }
+    }
+
+    return wav(&samples);
+}
+
+/// Returns a mono 16-bit WAV file of `samples` at [SAMPLE_RATE]
+fn wav(samples: &[i16]) -> Vec<u8> {
+    let data_size = samples.len() as u32 * 2;
+    let mut bytes = Vec::with_capacity(44 + data_size as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_size).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16_u32.to_le_bytes()); // the size of the format chunk
+    bytes.extend_from_slice(&1_u16.to_le_bytes()); // PCM
+    bytes.extend_from_slice(&1_u16.to_le_bytes()); // channels
+    bytes.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    bytes.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes()); // bytes per second
+    bytes.extend_from_slice(&2_u16.to_le_bytes()); // bytes per sample
+    bytes.extend_from_slice(&16_u16.to_le_bytes()); // bits per sample
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_size.to_le_bytes());
+    for sample in samples {
+        bytes.extend_from_slice(&sample.to_le_bytes());
+    }
+    return bytes;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_event_has_a_built_in_wav() {
+        for event in Event::ALL {
+            let bytes = built_in(event);
+            assert!(is_wav(&bytes), "{}", event);
+            let riff_size = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+            assert_eq!(riff_size as usize, bytes.len() - 8, "{}", event);
+            assert!(bytes.len() > 44, "{} is empty", event);
+        }
+    }
+}
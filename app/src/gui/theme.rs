@@ -0,0 +1,373 @@
+//! The colors and font the window is drawn with
+
+use std::path::Path;
+
+use macroquad::prelude::*;
+
+use super::{Skin, Tile};
+use crate::config::Config;
+
+/// The colors and font the window is drawn with.
+/// Colors are never the only cue: hidden cells are raised, flags, mines and numbers have glyphs, and the detonated mine is ringed.
+/// There are built-in themes, and custom ones are read from `[theme NAME]` sections of the [config file](crate::config)
+#[derive(Clone)]
+pub struct Theme {
+    pub name: String,
+    pub background: Color,
+    /// the lines between cells and around buttons
+    pub border: Color,
+    pub hidden: Color,
+    pub flagged: Color,
+    pub revealed: Color,
+    /// a revealed mine
+    pub mine: Color,
+    /// the outline of the keyboard cursor
+    pub cursor: Color,
+    /// menu text
+    pub text: Color,
+    /// flag poles, mines and crosses
+    pub glyph: Color,
+    /// the cloth of a flag
+    pub pennant: Color,
+    /// the lit segments of the mine counter and timer
+    pub counter: Color,
+    /// the color of each number from 1 to 8
+    pub numbers: [Color; 8],
+    /// the default font when [None]
+    pub font: Option<Font>,
+    /// cells are drawn as rectangles with the colors above when [None]
+    pub skin: Option<Skin>,
+}
+impl std::fmt::Debug for Theme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // macroquad's Font isn't Debug
+        return f
+            .debug_struct("Theme")
+            .field("name", &self.name)
+            .field("background", &self.background)
+            .field("numbers", &self.numbers)
+            .field("font", &self.font.as_ref().map(|_| "custom"))
+            .field("skin", &self.skin.as_ref().map(|skin| &skin.path))
+            .finish_non_exhaustive();
+    }
+}
+impl Default for Theme {
+    fn default() -> Self {
+        return Self::classic();
+    }
+}
+impl Theme {
+    /// Sky blue and greys, with the classic number colors: 1 blue, 2 green, 3 red, 4 navy, 5 maroon, 6 teal...
+    pub fn classic() -> Self {
+        return Self {
+            name: String::from("classic"),
+            background: SKYBLUE,
+            border: BLACK,
+            hidden: GRAY,
+            flagged: GREEN,
+            revealed: LIGHTGRAY,
+            mine: RED,
+            cursor: YELLOW,
+            text: BLACK,
+            glyph: BLACK,
+            pennant: RED,
+            counter: RED,
+            numbers: [
+                BLUE,
+                DARKGREEN,
+                RED,
+                DARKBLUE,
+                MAROON,
+                Color::new(0.0, 0.5, 0.5, 1.0),
+                BLACK,
+                DARKGRAY,
+            ],
+            font: None,
+            skin: None,
+        };
+    }
+
+    /// Dark greys with lighter number colors
+    pub fn dark() -> Self {
+        return Self {
+            name: String::from("dark"),
+            background: Color::new(0.08, 0.08, 0.1, 1.0),
+            border: Color::new(0.02, 0.02, 0.03, 1.0),
+            hidden: Color::new(0.3, 0.3, 0.33, 1.0),
+            flagged: Color::new(0.2, 0.38, 0.22, 1.0),
+            revealed: Color::new(0.16, 0.16, 0.18, 1.0),
+            mine: Color::new(0.6, 0.1, 0.1, 1.0),
+            cursor: GOLD,
+            text: Color::new(0.9, 0.9, 0.9, 1.0),
+            glyph: Color::new(0.9, 0.9, 0.9, 1.0),
+            pennant: Color::new(1.0, 0.35, 0.35, 1.0),
+            counter: Color::new(1.0, 0.25, 0.2, 1.0),
+            numbers: [
+                Color::new(0.45, 0.65, 1.0, 1.0),
+                Color::new(0.45, 0.85, 0.45, 1.0),
+                Color::new(1.0, 0.45, 0.45, 1.0),
+                Color::new(0.7, 0.55, 1.0, 1.0),
+                Color::new(1.0, 0.7, 0.3, 1.0),
+                Color::new(0.3, 0.85, 0.85, 1.0),
+                Color::new(0.9, 0.9, 0.9, 1.0),
+                Color::new(0.6, 0.6, 0.6, 1.0),
+            ],
+            font: None,
+            skin: None,
+        };
+    }
+
+    /// White on black with bright, saturated colors
+    pub fn high_contrast() -> Self {
+        return Self {
+            name: String::from("high-contrast"),
+            background: BLACK,
+            border: WHITE,
+            hidden: Color::new(0.35, 0.35, 0.35, 1.0),
+            flagged: YELLOW,
+            revealed: BLACK,
+            mine: RED,
+            cursor: MAGENTA,
+            text: WHITE,
+            glyph: WHITE,
+            pennant: RED,
+            counter: RED,
+            numbers: [
+                Color::new(0.3, 0.6, 1.0, 1.0),
+                Color::new(0.0, 1.0, 0.0, 1.0),
+                Color::new(1.0, 0.3, 0.3, 1.0),
+                YELLOW,
+                ORANGE,
+                Color::new(0.0, 1.0, 1.0, 1.0),
+                MAGENTA,
+                WHITE,
+            ],
+            font: None,
+            skin: None,
+        };
+    }
+
+    /// Blue and orange instead of green and red, for red-green color blindness with weak green cones
+    pub fn deuteranopia() -> Self {
+        return Self {
+            name: String::from("deuteranopia"),
+            background: Color::from_hex(0xdde6ee),
+            border: BLACK,
+            hidden: Color::from_hex(0x8a939c),
+            flagged: Color::from_hex(0x0072b2),
+            revealed: Color::from_hex(0xf0f0f0),
+            mine: Color::from_hex(0xd55e00),
+            cursor: Color::from_hex(0xe69f00),
+            text: BLACK,
+            glyph: BLACK,
+            pennant: Color::from_hex(0xe69f00),
+            counter: Color::from_hex(0xe69f00),
+            numbers: okabe_ito_numbers(),
+            font: None,
+            skin: None,
+        };
+    }
+
+    /// Blue and yellow instead of green and red, with a bright mine color since reds look dark with weak red cones
+    pub fn protanopia() -> Self {
+        return Self {
+            name: String::from("protanopia"),
+            mine: Color::from_hex(0xe69f00),
+            pennant: Color::from_hex(0xf0e442),
+            cursor: Color::from_hex(0xf0e442),
+            ..Self::deuteranopia()
+        };
+    }
+
+    /// Teal and red instead of blue and yellow, for blue-yellow color blindness
+    pub fn tritanopia() -> Self {
+        return Self {
+            name: String::from("tritanopia"),
+            background: Color::from_hex(0xe6e6e6),
+            border: BLACK,
+            hidden: Color::from_hex(0x8c8c8c),
+            flagged: Color::from_hex(0x2a9d9d),
+            revealed: Color::from_hex(0xf5f5f5),
+            mine: Color::from_hex(0xd81b60),
+            cursor: Color::from_hex(0xd81b60),
+            text: BLACK,
+            glyph: BLACK,
+            pennant: Color::from_hex(0xd81b60),
+            counter: Color::from_hex(0xff4081),
+            numbers: [
+                Color::from_hex(0x00767a),
+                Color::from_hex(0xc0392b),
+                BLACK,
+                Color::from_hex(0x8e2c62),
+                Color::from_hex(0x5a5a5a),
+                Color::from_hex(0x2a9d9d),
+                Color::from_hex(0xe0607e),
+                DARKGRAY,
+            ],
+            font: None,
+            skin: None,
+        };
+    }
+
+    /// The themes that are always available
+    pub fn built_ins() -> Vec<Self> {
+        return vec![
+            Self::classic(),
+            Self::dark(),
+            Self::high_contrast(),
+            Self::deuteranopia(),
+            Self::protanopia(),
+            Self::tritanopia(),
+        ];
+    }
+
+    /// Returns the built-in themes followed by the custom themes in `config`.
+    /// A custom theme starts as a copy of the theme named by its `base` key, or classic, then its other keys replace colors:
+    /// `background`, `border`, `hidden`, `flagged`, `revealed`, `mine`, `cursor`, `text`, `glyph`, `pennant`, `counter`,
+    /// `numbers` (up to 8 colors separated by commas), `font` (the path of a TTF file) and `skin` (the path of a [Skin] manifest).
+    /// Colors are written `#rrggbb` or `#rrggbbaa`, and relative paths start from `directory`, the config file's directory.
+    /// Keys that can't be used are skipped with a warning
+    pub fn load_all(config: &Config, directory: &Path) -> Vec<Self> {
+        let mut themes = Self::built_ins();
+
+        for section in config.sections() {
+            let Some(name) = section.name.strip_prefix("theme ") else {
+                continue;
+            };
+            let base = section.get("base").unwrap_or("classic");
+            let mut theme = match themes.iter().find(|theme| theme.name == base) {
+                Some(theme) => theme.clone(),
+                None => {
+                    eprintln!("warning: theme {}: there is no base theme {}", name, base);
+                    Self::classic()
+                }
+            };
+            theme.name = name.trim().to_string();
+            for (key, value) in &section.entries {
+                if let Err(error) = theme.set(key, value, directory) {
+                    eprintln!("warning: theme {}: {}", theme.name, error);
+                }
+            }
+
+            // a custom theme replaces a theme with the same name
+            themes.retain(|other| other.name != theme.name);
+            themes.push(theme);
+        }
+
+        return themes;
+    }
+
+    /// Sets the color or font called `key` from its config `value`. Relative paths start from `directory`
+    fn set(&mut self, key: &str, value: &str, directory: &Path) -> Result<(), String> {
+        let color = match key {
+            "base" => return Ok(()),
+            "font" => {
+                let bytes = std::fs::read(directory.join(value))
+                    .map_err(|error| format!("font {}: {}", value, error))?;
+                let font = load_ttf_font_from_bytes(&bytes)
+                    .map_err(|error| format!("font {}: {}", value, error))?;
+                self.font = Some(font);
+                return Ok(());
+            }
+            "skin" => {
+                let skin = Skin::load(&directory.join(value))
+                    .map_err(|error| format!("skin {}: {}", value, error))?;
+                self.skin = Some(skin);
+                return Ok(());
+            }
+            "numbers" => {
+                let colors: Vec<&str> = value.split(',').collect();
+                if colors.len() > self.numbers.len() {
+                    return Err(format!(
+                        "numbers has {} colors, at most 8 are used",
+                        colors.len()
+                    ));
+                }
+                for (number, color) in self.numbers.iter_mut().zip(colors) {
+                    *number = parse_color(color)?;
+                }
+                return Ok(());
+            }
+            "background" => &mut self.background,
+            "border" => &mut self.border,
+            "hidden" => &mut self.hidden,
+            "flagged" => &mut self.flagged,
+            "revealed" => &mut self.revealed,
+            "mine" => &mut self.mine,
+            "cursor" => &mut self.cursor,
+            "text" => &mut self.text,
+            "glyph" => &mut self.glyph,
+            "pennant" => &mut self.pennant,
+            "counter" => &mut self.counter,
+            unknown => return Err(format!("unknown key {}", unknown)),
+        };
+        *color = parse_color(value)?;
+        return Ok(());
+    }
+
+    /// The color a [Tile] is drawn with. Only the detonated mine has the mine color, the others are still hidden
+    pub fn tile_color(&self, tile: Tile) -> Color {
+        return match tile {
+            Tile::Hidden | Tile::Mine => self.hidden,
+            Tile::Flag => self.flagged,
+            Tile::WrongFlag | Tile::Revealed | Tile::Number(_) => self.revealed,
+            Tile::DetonatedMine => self.mine,
+        };
+    }
+
+    /// The color of a cell's number of `local_mines`
+    pub fn number_color(&self, local_mines: usize) -> Color {
+        return self.numbers[local_mines.clamp(1, 8) - 1];
+    }
+
+    /// Draws `text` in this theme's font, centered on the point (`x`, `y`)
+    pub fn draw_centered_text(&self, text: &str, x: f32, y: f32, font_size: f32, color: Color) {
+        let font = self.font.as_ref();
+        let dimensions = measure_text(text, font, font_size as u16, 1.0);
+        draw_text_ex(
+            text,
+            x - dimensions.width / 2.0,
+            y - dimensions.height / 2.0 + dimensions.offset_y,
+            TextParams {
+                font,
+                font_size: font_size as u16,
+                color,
+                ..Default::default()
+            },
+        );
+    }
+}
+
+/// The Okabe-Ito colors, which stay apart for every kind of color blindness
+fn okabe_ito_numbers() -> [Color; 8] {
+    return [
+        Color::from_hex(0x0072b2),
+        Color::from_hex(0x009e73),
+        Color::from_hex(0xd55e00),
+        Color::from_hex(0x000000),
+        Color::from_hex(0xcc79a7),
+        Color::from_hex(0x56b4e9),
+        Color::from_hex(0xe69f00),
+        DARKGRAY,
+    ];
+}
+
+/// Parses a color written `#rrggbb` or `#rrggbbaa`
+fn parse_color(text: &str) -> Result<Color, String> {
+    let text = text.trim();
+    let invalid = || format!("{} is not a color like #87ceeb", text);
+    let hex = text.strip_prefix('#').ok_or_else(invalid)?;
+    if !matches!(hex.len(), 6 | 8) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    let value = u32::from_str_radix(hex, 16).map_err(|_| invalid())?;
+    return Ok(match hex.len() {
+        6 => Color::from_hex(value),
+        _ => Color::from_rgba(
+            (value >> 24) as u8,
+            (value >> 16) as u8,
+            (value >> 8) as u8,
+            value as u8,
+        ),
+    });
+}
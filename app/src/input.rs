@@ -0,0 +1,46 @@
+use std::io::{stdin, stdout, Write};
+
+/// Clear the terminal by writing a special string to the standard output.
+/// # Errors
+/// - When cannot [write] to [stdout]
+pub fn clear_terminal() -> Result<(), std::io::Error> {
+    write!(stdout(), "\x1B[2J\x1B[1;1H")?;
+    return Ok(());
+}
+
+/// This function returns `false` if the user enters "y" or "yes". Otherwise this function returns `true`.
+/// - Not case sensitive
+/// - #### fails when [get_input] fails
+pub fn quit_terminal() -> Result<bool, std::io::Error> {
+    return match get_input("Enter \"yes\" to play again\n")?
+        .to_lowercase()
+        .as_str()
+    {
+        "y" | "yes" => Ok(false),
+        _ => Ok(true),
+    };
+}
+
+/// This function will display the `prompt` to the user using `standard output stream` ([std::io::Stdout]). <br>
+/// A line of input is read from `standard input stream` ([std::io::Stdin]) and returned.
+/// - Trailing and leading whitespace is trimmed from the input line.
+/// # Errors
+/// - If cannot write `prompt` to `standard output stream`
+/// - If cannot flush `standard output stream`
+/// - If cannot read input from `standard input stream`
+/// - If `standard input stream` has ended
+pub fn get_input(prompt: &str) -> Result<String, std::io::Error> {
+    // prompt the user
+    stdout().write_all(prompt.as_bytes())?; // write the prompt to `stdout`
+    stdout().flush()?; // flush the standard output stream (ensure all data reaches its destination ie the terminal)
+
+    // read a line of input
+    let mut input = String::new(); // create a [String] to hold user input
+    if stdin().read_line(&mut input)? == 0 {
+        // nothing more will ever be read, so don't let callers loop forever
+        return Err(std::io::ErrorKind::UnexpectedEof.into());
+    }
+    let input = input.trim().to_string(); // shadow `input` with a clone that doesn't include leading or trailing whitespace
+
+    return Ok(input);
+}
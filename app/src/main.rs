@@ -0,0 +1,111 @@
+//! The minesweeper front ends, on top of the `minesweeper` engine. Each is behind a cargo feature:
+//! - `gui`: the macroquad window in [gui]
+//! - `terminal`: the stdin/stdout front end in [terminal], the full-screen one in [tui] and the screen reader one in [accessible]
+
+// the explicit `return` is this crate's style
+#![allow(clippy::needless_return)]
+
+// modules
+mod cli;
+#[cfg(feature = "gui")]
+mod config;
+#[cfg(feature = "gui")]
+mod gui;
+
+#[cfg(feature = "terminal")]
+mod accessible;
+#[cfg(feature = "terminal")]
+mod ansi;
+#[cfg(feature = "terminal")]
+mod command_line;
+#[cfg(feature = "terminal")]
+mod input;
+#[cfg(feature = "terminal")]
+mod terminal;
+#[cfg(feature = "terminal")]
+mod tui;
+
+use cli::Options;
+
+use color_eyre::{eyre::eyre, Report};
+use minesweeper::game::{Difficulty, Game, HEIGHT, WIDTH};
+
+fn main() -> Result<(), Report> {
+    color_eyre::install()?;
+
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(2);
+        }
+    };
+
+    if options.help {
+        print!("{}", cli::help());
+        return Ok(());
+    }
+
+    // the gui has a menu to pick the difficulty, so it picks the board size itself
+    #[cfg(feature = "gui")]
+    if let cli::Mode::Gui = options.mode {
+        let path = options.config.clone().or_else(config::default_path);
+        let (config, path) = load_config(path);
+        macroquad::Window::new(
+            "Minesweeper",
+            gui::run(options.difficulty, options.seed, config, path),
+        );
+        return Ok(());
+    }
+
+    // the board size is a const parameter, so each difficulty needs its own instance of `play`
+    return match options.difficulty {
+        Difficulty::Beginner => play::<9, 9>(options),
+        Difficulty::Intermediate => play::<16, 16>(options),
+        Difficulty::Expert => play::<30, 16>(options),
+        Difficulty::Large => play::<WIDTH, HEIGHT>(options),
+    };
+}
+
+/// Reads the config file at `path`. A file that can't be read is skipped with a warning,
+/// and its path is dropped so the settings aren't saved over it
+#[cfg(feature = "gui")]
+fn load_config(path: Option<std::path::PathBuf>) -> (config::Config, Option<std::path::PathBuf>) {
+    let Some(path) = path else {
+        return (config::Config::default(), None);
+    };
+    return match config::Config::load(&path) {
+        Ok(config) => (config, Some(path)),
+        Err(error) => {
+            eprintln!("warning: ignoring {}: {}", path.display(), error);
+            (config::Config::default(), None)
+        }
+    };
+}
+
+/// Plays a `W`x`H` game with the front end chosen by `options`
+fn play<const W: usize, const H: usize>(options: Options) -> Result<(), Report> {
+    debug_assert_eq!(
+        (W, H),
+        (options.difficulty.width(), options.difficulty.height())
+    );
+
+    let is_mine_percentage = options.difficulty.is_mine_percentage();
+    // only the terminal front ends are played from here
+    #[cfg_attr(not(feature = "terminal"), allow(unused_variables))]
+    let game: Game<W, H> = match options.seed {
+        Some(seed) => Game::seeded(is_mine_percentage, seed),
+        None => Game::new(is_mine_percentage),
+    };
+
+    return match options.mode {
+        #[cfg(feature = "terminal")]
+        cli::Mode::Terminal => Ok(terminal::run(game)?),
+        #[cfg(feature = "terminal")]
+        cli::Mode::Tui => Ok(tui::run(game)?),
+        #[cfg(feature = "terminal")]
+        cli::Mode::Accessible => Ok(accessible::run(game)?),
+        #[allow(unreachable_patterns)]
+        mode => Err(eyre!("this build doesn't include the {:?} front end", mode)),
+    };
+}
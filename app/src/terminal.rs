@@ -0,0 +1,118 @@
+//! The terminal front end
+
+use std::{
+    collections::VecDeque,
+    io::{stdout, Write},
+};
+
+use crate::{
+    ansi::{ColoredBoard, Styling},
+    command_line::{self, SYNTAX_HELP},
+    input::{clear_terminal, get_input, quit_terminal},
+};
+use minesweeper::{
+    frontend::{self, CommandSource, Renderer},
+    game::{Command, Game, GameState},
+    label::cell_label,
+};
+
+/// Explains the symbols of a finished board
+pub const OUTCOME_KEY: &str = "⚑ flagged mine  X wrong flag  * missed mine  ! detonated mine";
+
+/// Plays `game` in the terminal until the player quits
+pub fn run<const W: usize, const H: usize>(game: Game<W, H>) -> Result<(), std::io::Error> {
+    return frontend::run(game, &mut Printer::default(), &mut Prompt::default());
+}
+
+/// Prints the board to [stdout], colored when it is a terminal
+#[derive(Debug, Clone, Copy)]
+pub struct Printer {
+    styling: Styling,
+}
+impl Default for Printer {
+    fn default() -> Self {
+        return Self {
+            styling: Styling::detect(),
+        };
+    }
+}
+impl Renderer for Printer {
+    type Error = std::io::Error;
+    fn render<const W: usize, const H: usize>(
+        &mut self,
+        game: &Game<W, H>,
+    ) -> Result<(), Self::Error> {
+        // plain output may be piped into a file, which should keep every move
+        if self.styling != Styling::Plain {
+            clear_terminal()?;
+        }
+        if game.state().is_finished() {
+            let message = match game.state() {
+                GameState::Won => "YOU CLEARED THE BOARD",
+                _ => "YOU REVEALED A MINE\nGAME OVER",
+            };
+            writeln!(
+                stdout(),
+                "{} IN {}s\n{}\n{}",
+                message,
+                game.elapsed().as_secs(),
+                ColoredBoard::new(game.board(), self.styling)
+                    .finished(true)
+                    .last_move(game.last_move()),
+                OUTCOME_KEY
+            )?;
+        } else {
+            writeln!(
+                stdout(),
+                "{}",
+                ColoredBoard::new(game.board(), self.styling).last_move(game.last_move())
+            )?;
+            if let Some(hint) = game.hint() {
+                writeln!(stdout(), "Hint: {} is safe", cell_label(hint))?;
+            }
+            writeln!(stdout(), "{}\n", SYNTAX_HELP)?;
+        }
+        return Ok(());
+    }
+}
+
+/// Reads [command lines](crate::command_line) from [stdin](std::io::stdin), a batch of commands at a time
+#[derive(Debug, Default, Clone)]
+pub struct Prompt {
+    /// commands from the last line that haven't been played yet
+    queued: VecDeque<Command>,
+}
+impl CommandSource for Prompt {
+    type Error = std::io::Error;
+    fn next_command<const W: usize, const H: usize>(
+        &mut self,
+        game: &Game<W, H>,
+    ) -> Result<Option<Command>, Self::Error> {
+        if game.state().is_finished() {
+            self.queued.clear();
+            return Ok(Some(if quit_terminal()? {
+                Command::Quit
+            } else {
+                Command::NewGame
+            }));
+        }
+        if let Some(command) = self.queued.pop_front() {
+            return Ok(Some(command));
+        }
+        loop {
+            let line = get_input("> ")?;
+            match command_line::parse::<W, H>(&line) {
+                Ok(commands) => {
+                    self.queued.extend(commands);
+                    if let Some(command) = self.queued.pop_front() {
+                        return Ok(Some(command));
+                    }
+                }
+                // the prompt is 2 characters wide, so the report lines up with the input
+                Err(parse_error) => {
+                    println!("  {}\n", parse_error.report(&line).replace('\n', "\n  "))
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,388 @@
+//! The full-screen terminal front end. The board is redrawn in place and a cursor selects the cell to act on
+
+use std::{
+    cell::RefCell,
+    io::{stdout, Write},
+    ops::Range,
+    rc::Rc,
+    time::Duration,
+};
+
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::Print,
+    terminal::{
+        self, disable_raw_mode, enable_raw_mode, Clear, ClearType, EnterAlternateScreen,
+        LeaveAlternateScreen,
+    },
+};
+
+use crate::ansi::{ColoredBoard, Styling};
+use minesweeper::{
+    frontend::{self, CommandSource, Renderer},
+    game::{Action, Command, Game, GameState},
+    label::{cell_label, column_label, row_label},
+    layout::Border,
+};
+
+/// How long [Keyboard] waits for a key before returning, so the timer on the status line keeps counting
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// The keys, shown under the status line
+const KEY_HELP: &str = "arrows/hjkl move  shift+arrows/HJKL pan  space reveal  f flag  c chord  u undo  ? hint  n new game  q quit";
+
+/// The lines under the board: a blank line, the status line and [KEY_HELP]
+const STATUS_HEIGHT: usize = 3;
+
+/// Plays `game` full-screen in the terminal until the player quits
+pub fn run<const W: usize, const H: usize>(game: Game<W, H>) -> Result<(), std::io::Error> {
+    let _raw_mode = RawMode::enable()?;
+    let viewport = Rc::new(RefCell::new(Viewport::default()));
+    return frontend::run(
+        game,
+        &mut Screen::new(viewport.clone()),
+        &mut Keyboard::new(viewport),
+    );
+}
+
+/// Keeps the terminal in raw mode on the alternate screen until dropped
+struct RawMode;
+impl RawMode {
+    fn enable() -> Result<Self, std::io::Error> {
+        enable_raw_mode()?;
+        // restores the terminal even if entering the alternate screen fails
+        let raw_mode = RawMode;
+        execute!(stdout(), EnterAlternateScreen, Hide)?;
+        return Ok(raw_mode);
+    }
+}
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        // there's nowhere left to report errors to
+        let _ = execute!(stdout(), Show, LeaveAlternateScreen);
+        let _ = disable_raw_mode();
+    }
+}
+
+/// The part of the board that fits in the terminal. It follows the cursor, and can be panned away from it
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Viewport {
+    /// the first row shown
+    top: usize,
+    /// the first column shown
+    left: usize,
+    /// how many rows fit, measured at the last render
+    rows: usize,
+    /// how many columns fit, measured at the last render
+    columns: usize,
+    /// where the cursor was at the last render. The view only scrolls to the cursor when it moves
+    cursor: Option<(usize, usize)>,
+}
+impl Viewport {
+    /// Fits `rows`x`columns` cells of a `W`x`H` board, scrolling to show `cursor` if it moved
+    fn update<const W: usize, const H: usize>(
+        &mut self,
+        rows: usize,
+        columns: usize,
+        cursor: (usize, usize),
+    ) {
+        self.rows = rows.min(H);
+        self.columns = columns.min(W);
+        if self.cursor != Some(cursor) {
+            self.cursor = Some(cursor);
+            self.top = scroll_to(self.top, self.rows, cursor.0);
+            self.left = scroll_to(self.left, self.columns, cursor.1);
+        }
+        self.top = self.top.min(H - self.rows);
+        self.left = self.left.min(W - self.columns);
+    }
+
+    /// Moves the view by a quarter of its size in each direction given by the sign of `rows` and `columns`
+    fn pan<const W: usize, const H: usize>(&mut self, rows: isize, columns: isize) {
+        let row_step = (self.rows / 4).max(1) as isize;
+        let column_step = (self.columns / 4).max(1) as isize;
+        self.top = self
+            .top
+            .saturating_add_signed(rows * row_step)
+            .min(H.saturating_sub(self.rows));
+        self.left = self
+            .left
+            .saturating_add_signed(columns * column_step)
+            .min(W.saturating_sub(self.columns));
+    }
+
+    /// The rows shown
+    fn rows(&self) -> Range<usize> {
+        return self.top..self.top + self.rows;
+    }
+    /// The columns shown
+    fn columns(&self) -> Range<usize> {
+        return self.left..self.left + self.columns;
+    }
+
+    /// Describes which part of a `W`x`H` board is shown, or returns [None] if all of it is
+    fn position<const W: usize, const H: usize>(&self) -> Option<String> {
+        if self.rows >= H && self.columns >= W {
+            return None;
+        }
+        return Some(format!(
+            "Rows {}-{}/{}  Columns {}-{}/{}",
+            row_label(self.top),
+            row_label(self.rows().end - 1),
+            H,
+            column_label(self.left),
+            column_label(self.columns().end - 1),
+            W
+        ));
+    }
+}
+
+/// Returns the start of a `length` long range that contains `index`, moving `start` as little as possible
+fn scroll_to(start: usize, length: usize, index: usize) -> usize {
+    if index < start {
+        return index;
+    }
+    if index >= start + length {
+        return index + 1 - length;
+    }
+    return start;
+}
+
+/// Redraws the part of the board in the [Viewport], the cursor and a status line in place
+#[derive(Debug, Clone)]
+pub struct Screen {
+    styling: Styling,
+    viewport: Rc<RefCell<Viewport>>,
+}
+impl Screen {
+    /// Colored unless `NO_COLOR` is set. The cursor needs escape codes, so output is never [Styling::Plain]
+    pub fn new(viewport: Rc<RefCell<Viewport>>) -> Self {
+        let styling = match Styling::detect() {
+            Styling::Color => Styling::Color,
+            _ => Styling::Monochrome,
+        };
+        return Self { styling, viewport };
+    }
+}
+impl Renderer for Screen {
+    type Error = std::io::Error;
+    fn render<const W: usize, const H: usize>(
+        &mut self,
+        game: &Game<W, H>,
+    ) -> Result<(), Self::Error> {
+        let mut stdout = stdout();
+        let board = game.board();
+        let layout = board.layout(Border::Box);
+        let (width, height) = terminal::size()?;
+        let (rows, columns) = layout.fit(
+            width as usize,
+            (height as usize).saturating_sub(STATUS_HEIGHT),
+        );
+        let mut viewport = self.viewport.borrow_mut();
+        viewport.update::<W, H>(rows, columns, game.cell_index());
+
+        let board = ColoredBoard::new(board, self.styling)
+            .finished(game.state().is_finished())
+            .layout(layout.window(viewport.rows(), viewport.columns()))
+            .selected(Some(game.cell_index()))
+            .last_move(game.last_move());
+
+        queue!(stdout, MoveTo(0, 0))?;
+        // raw mode doesn't return to the start of the line on a line feed
+        for line in board.to_string().lines() {
+            queue!(
+                stdout,
+                Print(line),
+                Clear(ClearType::UntilNewLine),
+                Print("\r\n")
+            )?;
+        }
+
+        let status = match game.state() {
+            GameState::GameOver => {
+                "YOU REVEALED A MINE - GAME OVER - X wrong flag  * missed mine  ! detonated"
+            }
+            GameState::Won => "YOU CLEARED THE BOARD",
+            _ => "",
+        };
+        let status = format!(
+            "{}  Mines: {}  Time: {}s  {}  {}",
+            cell_label(game.cell_index()),
+            game.remaining_mines(),
+            game.elapsed().as_secs(),
+            viewport.position::<W, H>().unwrap_or_default(),
+            status
+        );
+        // lines that wrap would scroll the board off the top of the screen
+        let truncate = |line: &str| line.chars().take(width as usize).collect::<String>();
+        queue!(
+            stdout,
+            Print("\r\n"),
+            Print(truncate(&status)),
+            Clear(ClearType::UntilNewLine),
+            Print("\r\n"),
+            Print(truncate(KEY_HELP)),
+            Clear(ClearType::FromCursorDown),
+        )?;
+
+        return stdout.flush();
+    }
+}
+
+/// Turns key presses into [Command]s. Movement keys [select](Command::Select) a cell, the rest act on the selection.
+/// Panning keys move the [Viewport] without making a [Command]
+#[derive(Debug, Clone)]
+pub struct Keyboard {
+    viewport: Rc<RefCell<Viewport>>,
+}
+impl Keyboard {
+    pub fn new(viewport: Rc<RefCell<Viewport>>) -> Self {
+        return Self { viewport };
+    }
+}
+impl CommandSource for Keyboard {
+    type Error = std::io::Error;
+    fn next_command<const W: usize, const H: usize>(
+        &mut self,
+        game: &Game<W, H>,
+    ) -> Result<Option<Command>, Self::Error> {
+        if !event::poll(POLL_INTERVAL)? {
+            return Ok(None);
+        }
+        let Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event::read()?
+        else {
+            return Ok(None);
+        };
+
+        let pan = |rows, columns| {
+            self.viewport.borrow_mut().pan::<W, H>(rows, columns);
+            return None;
+        };
+        let (row_index, column_index) = game.cell_index();
+        let select = |row_index, column_index| {
+            return Some(Command::Select {
+                cell_index: (row_index, column_index),
+            });
+        };
+        let act = |action| {
+            return Some(Command::Act {
+                action,
+                cell_index: game.cell_index(),
+            });
+        };
+
+        return Ok(match code {
+            // raw mode stops ctrl+c from interrupting, so treat it as quitting
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => Some(Command::Quit),
+            KeyCode::Up if modifiers.contains(KeyModifiers::SHIFT) => pan(-1, 0),
+            KeyCode::Down if modifiers.contains(KeyModifiers::SHIFT) => pan(1, 0),
+            KeyCode::Left if modifiers.contains(KeyModifiers::SHIFT) => pan(0, -1),
+            KeyCode::Right if modifiers.contains(KeyModifiers::SHIFT) => pan(0, 1),
+            KeyCode::Char('K') => pan(-1, 0),
+            KeyCode::Char('J') => pan(1, 0),
+            KeyCode::Char('H') => pan(0, -1),
+            KeyCode::Char('L') => pan(0, 1),
+            KeyCode::Up | KeyCode::Char('k') => select(row_index.saturating_sub(1), column_index),
+            KeyCode::Down | KeyCode::Char('j') => select((row_index + 1).min(H - 1), column_index),
+            KeyCode::Left | KeyCode::Char('h') => select(row_index, column_index.saturating_sub(1)),
+            KeyCode::Right | KeyCode::Char('l') => select(row_index, (column_index + 1).min(W - 1)),
+            KeyCode::Char(' ') | KeyCode::Enter => act(Action::Reveal),
+            KeyCode::Char('f') => match game.board()[game.cell_index()].is_flagged() {
+                true => act(Action::Unflag),
+                false => act(Action::Flag),
+            },
+            KeyCode::Char('c') => act(Action::Chord),
+            KeyCode::Char('u') => Some(Command::Undo),
+            KeyCode::Char('?') => Some(Command::Hint),
+            KeyCode::Char('n') => Some(Command::NewGame),
+            KeyCode::Char('q') | KeyCode::Esc => Some(Command::Quit),
+            _ => None,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scrolling_shows_the_index_with_the_least_movement() {
+        // already shown
+        assert_eq!(scroll_to(10, 5, 12), 10);
+        assert_eq!(scroll_to(10, 5, 14), 10);
+        // past the end, it becomes the last one shown
+        assert_eq!(scroll_to(10, 5, 15), 11);
+        assert_eq!(scroll_to(10, 5, 30), 26);
+        // before the start, it becomes the first one shown
+        assert_eq!(scroll_to(10, 5, 9), 9);
+        assert_eq!(scroll_to(10, 5, 0), 0);
+    }
+
+    #[test]
+    fn the_view_follows_the_cursor_past_each_edge() {
+        let mut viewport = Viewport::default();
+        viewport.update::<30, 20>(5, 8, (0, 0));
+        assert_eq!((viewport.rows(), viewport.columns()), (0..5, 0..8));
+
+        // past the bottom and the right
+        viewport.update::<30, 20>(5, 8, (6, 9));
+        assert_eq!((viewport.rows(), viewport.columns()), (2..7, 2..10));
+        viewport.update::<30, 20>(5, 8, (19, 29));
+        assert_eq!((viewport.rows(), viewport.columns()), (15..20, 22..30));
+
+        // past the top and the left
+        viewport.update::<30, 20>(5, 8, (14, 21));
+        assert_eq!((viewport.rows(), viewport.columns()), (14..19, 21..29));
+        viewport.update::<30, 20>(5, 8, (0, 0));
+        assert_eq!((viewport.rows(), viewport.columns()), (0..5, 0..8));
+    }
+
+    #[test]
+    fn the_view_stays_panned_until_the_cursor_moves() {
+        let mut viewport = Viewport::default();
+        viewport.update::<30, 20>(5, 8, (0, 0));
+        viewport.pan::<30, 20>(1, 1);
+        viewport.update::<30, 20>(5, 8, (0, 0));
+        assert_eq!((viewport.rows(), viewport.columns()), (1..6, 2..10));
+
+        viewport.update::<30, 20>(5, 8, (0, 1));
+        assert_eq!((viewport.rows(), viewport.columns()), (0..5, 1..9));
+    }
+
+    #[test]
+    fn panning_stops_at_the_board_edges() {
+        let mut viewport = Viewport::default();
+        viewport.update::<30, 20>(5, 8, (0, 0));
+        viewport.pan::<30, 20>(-1, -1);
+        assert_eq!((viewport.rows(), viewport.columns()), (0..5, 0..8));
+
+        for _ in 0..100 {
+            viewport.pan::<30, 20>(1, 1);
+        }
+        assert_eq!((viewport.rows(), viewport.columns()), (15..20, 22..30));
+    }
+
+    #[test]
+    fn a_board_that_fits_has_no_position() {
+        let mut viewport = Viewport::default();
+        viewport.update::<9, 9>(40, 80, (4, 4));
+        assert_eq!((viewport.rows(), viewport.columns()), (0..9, 0..9));
+        assert_eq!(viewport.position::<9, 9>(), None);
+        // and can't be panned
+        viewport.pan::<9, 9>(1, 1);
+        assert_eq!((viewport.rows(), viewport.columns()), (0..9, 0..9));
+
+        viewport.update::<30, 20>(5, 8, (0, 0));
+        assert_eq!(
+            viewport.position::<30, 20>().as_deref(),
+            Some("Rows 1-5/20  Columns A-H/30")
+        );
+    }
+}
@@ -1,15 +1,64 @@
 use std::{
+    collections::VecDeque,
     fmt::Display,
     ops::{Index, IndexMut},
 };
 
-use crate::cell::Cell;
+use rand::{thread_rng, Rng};
+
+use crate::{
+    cell::Cell,
+    layout::{Border, GridLayout},
+};
 
 /// A 2 dimensional board of `WIDTH` x `HEIGHT` [Cell]s in area.
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Board<const WIDTH: usize, const HEIGHT: usize> {
     cells: [[Cell; WIDTH]; HEIGHT],
+    topology: Topology,
+}
+
+/// The `(row, column)` offsets from a [Cell] to each of its eight neighbors
+const NEIGHBOR_OFFSETS: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Describes how the edges of a [Board] connect, which decides what the neighbors of a [Cell] are
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Topology {
+    /// Cells on the edge of the board have fewer neighbors
+    #[default]
+    Bounded,
+}
+impl Topology {
+    /// Applies a `(row, column)` offset to `index` on a `W`x`H` board. Returns [None] if the result is off the board
+    fn offset<const W: usize, const H: usize>(
+        self,
+        index: (usize, usize),
+        offset: (isize, isize),
+    ) -> Option<(usize, usize)> {
+        return match self {
+            Topology::Bounded => {
+                let row = index
+                    .0
+                    .checked_add_signed(offset.0)
+                    .filter(|&row| row < H)?;
+                let column = index
+                    .1
+                    .checked_add_signed(offset.1)
+                    .filter(|&column| column < W)?;
+                Some((row, column))
+            }
+        };
+    }
 }
 impl<const W: usize, const H: usize> Index<(usize, usize)> for Board<W, H> {
     type Output = Cell;
@@ -25,38 +74,36 @@ impl<const W: usize, const H: usize> IndexMut<(usize, usize)> for Board<W, H> {
 impl<const W: usize, const H: usize> Display for Board<W, H> {
     /// displays a board as a grid. rows delimited by new line, cells delimited by a space
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "   ")?;
-        for column_index in 0..W {
-            write!(f, "{} ", column_index)?;
-        }
-        write!(f, "\n   {}\n", String::from("-".repeat(W * 2)))?;
-        for (row_index, row) in self.cells.iter().enumerate() {
-            write!(f, "{} |", row_index)?;
-            for element in row.iter() {
-                write!(f, "{} ", element)?;
-            }
-            write!(f, "\n")?;
-        }
-        return Ok(());
+        return self.fmt_grid(f, &self.layout(Border::Ascii), |_, text| text);
     }
 }
 impl<const W: usize, const H: usize> Board<W, H> {
     /// Initialize the minesweeper board with random true/false
     pub fn random(is_mine_percentage: f64) -> Self {
-        // create cells that have a 50% chance of being a mine
-        let cells = Board::random_cells(is_mine_percentage);
-        let cells = Board::initialize_local_mines(cells);
+        return Self::random_with(is_mine_percentage, &mut thread_rng());
+    }
+
+    /// Initialize the minesweeper board from a specific random number generator, such as a seeded one
+    pub fn random_with(is_mine_percentage: f64, rng: &mut impl Rng) -> Self {
+        // create cells that have a `is_mine_percentage` chance of being a mine
+        let cells = Board::random_cells(is_mine_percentage, rng);
 
-        return Board { cells: cells };
+        let mut board = Board {
+            cells,
+            topology: Topology::Bounded,
+        };
+        board.initialize_local_mines();
+
+        return board;
     }
 
     /// A helper associated function that returns a `W`x`H` 2d array of [Cell]s that each have a `is_mine_percentage` of being a mine
-    fn random_cells(is_mine_percentage: f64) -> [[Cell; W]; H] {
+    fn random_cells(is_mine_percentage: f64, rng: &mut impl Rng) -> [[Cell; W]; H] {
         let mut cells = [[Cell::CLEAR; W]; H];
 
         for row in cells.iter_mut() {
             for cell in row.iter_mut() {
-                *cell = Cell::random(is_mine_percentage);
+                *cell = Cell::random_with(is_mine_percentage, rng);
             }
         }
 
@@ -64,56 +111,160 @@ impl<const W: usize, const H: usize> Board<W, H> {
     }
 
     /// Initializes all of the `cell`s `local_mines` field.
-    fn initialize_local_mines(mut cells: [[Cell; W]; H]) -> [[Cell; W]; H] {
-        // I didn't use an iterator bc I couldn't wrap my head around the logic for this given scenario...
-        // It needed to be able to iterate across local cells within the board while also iterating across the entire board
-        for row in 0..W {
-            for column in 0..H {
-                // count the mines local to this cell
-                let local_mine_count = Self::count_local_mines(&cells, (row, column));
-
-                // set the local mine count for the given cell, based on the count accumulated
-                cells[row][column].set_local_mines(local_mine_count);
-            }
-        }
+    fn initialize_local_mines(&mut self) {
+        for index in self.indices() {
+            // count the mines local to this cell
+            let local_mine_count = self.count_local_mines(index);
 
-        return cells;
+            // set the local mine count for the given cell, based on the count accumulated
+            self[index].set_local_mines(local_mine_count);
+        }
     }
 
     /// count the number of [Cell]s that are mines surrounding a [Cell] at the specified indices
-    fn count_local_mines(cells: &[[Cell; W]; H], index: (usize, usize)) -> usize {
-        let mut local_mine_count = 0;
-
-        // if the cell in question is not a mine
-        if !cells[index.0][index.1].is_mine() {
-            // list indices of all neighboring cells
-            let neighbor_indices = [
-                (index.0 as isize - 1, index.1 as isize - 1),
-                (index.0 as isize - 1, index.1 as isize - 0),
-                (index.0 as isize - 1, index.1 as isize + 1),
-                (index.0 as isize - 0, index.1 as isize - 1),
-                (index.0 as isize + 0, index.1 as isize + 1),
-                (index.0 as isize + 1, index.1 as isize - 1),
-                (index.0 as isize + 1, index.1 as isize + 0),
-                (index.0 as isize + 1, index.1 as isize + 1),
-            ];
-
-            for (neighbor_row, neighbor_column) in neighbor_indices {
-                // if the neighbor is in bounds
-                if neighbor_row >= 0
-                    && neighbor_column >= 0
-                    && neighbor_row < H as isize
-                    && neighbor_column < W as isize
-                {
-                    // Increment the local mine count
-                    if cells[neighbor_row as usize][neighbor_column as usize].is_mine() {
-                        local_mine_count += 1;
-                    }
-                }
+    fn count_local_mines(&self, index: (usize, usize)) -> usize {
+        // a mine doesn't display a count
+        if self[index].is_mine() {
+            return 0;
+        }
+        return self
+            .neighbors(index)
+            .filter(|&neighbor| self[neighbor].is_mine())
+            .count();
+    }
+
+    /// A board with mines at `mines` and every other cell clear, for tests
+    #[cfg(test)]
+    pub(crate) fn with_mines(mines: &[(usize, usize)]) -> Self {
+        let mut cells = [[Cell::CLEAR; W]; H];
+        for &(row, column) in mines {
+            cells[row][column] = Cell::random_with(1.0, &mut thread_rng());
+        }
+        let mut board = Board {
+            cells,
+            topology: Topology::Bounded,
+        };
+        board.initialize_local_mines();
+        return board;
+    }
+
+    /// Returns the [Topology] used to find neighbors
+    pub const fn topology(&self) -> Topology {
+        return self.topology;
+    }
+
+    /// Returns an iterator over every index on the [Board] in row major order
+    pub fn indices(&self) -> impl Iterator<Item = (usize, usize)> {
+        return (0..H).flat_map(|row| (0..W).map(move |column| (row, column)));
+    }
+
+    /// Returns an iterator over the indices of the [Cell]s surrounding `index`.
+    /// - Which indices are neighbors is decided by the [Board]'s [Topology]
+    /// - The iterator doesn't borrow the [Board], so it may be mutated while iterating
+    pub fn neighbors(&self, index: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        let topology = self.topology;
+        return NEIGHBOR_OFFSETS
+            .into_iter()
+            .filter_map(move |offset| topology.offset::<W, H>(index, offset));
+    }
+
+    /// Returns the number of flagged [Cell]s surrounding `index`
+    pub fn neighbor_flags(&self, index: (usize, usize)) -> usize {
+        return self
+            .neighbors(index)
+            .filter(|&neighbor| self[neighbor].is_flagged())
+            .count();
+    }
+
+    /// Returns an iterator over the indices of the [Cell]s surrounding `index` that are neither revealed nor flagged
+    pub fn hidden_neighbors(
+        &self,
+        index: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        return self.neighbors(index).filter(|&neighbor| {
+            let cell = self[neighbor];
+            !cell.is_revealed() && !cell.is_flagged()
+        });
+    }
+
+    /// Reveals the [Cell] at `index`. If it has no local mines, its neighbors are revealed too, cascading outwards.
+    /// - Flagged [Cell]s are never revealed
+    /// - Returns the indices of the newly revealed [Cell]s, nearest to `index` first
+    pub fn reveal(&mut self, index: (usize, usize)) -> Vec<(usize, usize)> {
+        let mut revealed = Vec::new();
+        if self.get_cell(index).is_none() {
+            return revealed;
+        }
+
+        let mut queue = VecDeque::from([index]);
+        while let Some(index) = queue.pop_front() {
+            let cell = &mut self[index];
+            if cell.is_revealed() || cell.is_flagged() {
+                continue;
+            }
+            cell.reveal();
+            revealed.push(index);
+
+            if !cell.is_mine() && cell.local_mines() == 0 {
+                queue.extend(self.hidden_neighbors(index));
             }
         }
 
-        return local_mine_count;
+        return revealed;
+    }
+
+    /// Returns a [GridLayout] for displaying this board with the given [Border]
+    pub fn layout(&self, border: Border) -> GridLayout {
+        // every cell is displayed as a single character
+        return GridLayout::new(W, H, 1, border);
+    }
+
+    /// Writes the board as a grid laid out by `layout`, see [Board::layout].
+    /// - `style_cell` is given each cell's index and padded text, and returns the text to write. This lets renderers add color without breaking the alignment
+    pub fn fmt_grid(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        layout: &GridLayout,
+        style_cell: impl FnMut((usize, usize), String) -> String,
+    ) -> std::fmt::Result {
+        return layout.write(f, |index| self[index].to_string(), style_cell);
+    }
+
+    /// Like [Board::fmt_grid], but shows each cell's [Outcome](crate::cell::Outcome), for a board whose game is over
+    pub fn fmt_outcomes(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        layout: &GridLayout,
+        style_cell: impl FnMut((usize, usize), String) -> String,
+    ) -> std::fmt::Result {
+        return layout.write(f, |index| self[index].outcome().to_string(), style_cell);
+    }
+
+    /// Returns the number of mines on the [Board]
+    pub fn mine_count(&self) -> usize {
+        return self
+            .cells
+            .iter()
+            .flatten()
+            .filter(|cell| cell.is_mine())
+            .count();
+    }
+    /// Returns the number of flagged [Cell]s on the [Board]
+    pub fn flag_count(&self) -> usize {
+        return self
+            .cells
+            .iter()
+            .flatten()
+            .filter(|cell| cell.is_flagged())
+            .count();
+    }
+    /// Returns true when every [Cell] that isn't a mine has been revealed
+    pub fn is_cleared(&self) -> bool {
+        return self
+            .cells
+            .iter()
+            .flatten()
+            .all(|cell| cell.is_mine() || cell.is_revealed());
     }
 
     /// This reflective function returns the first const parameter
@@ -145,9 +296,40 @@ impl<const W: usize, const H: usize> Board<W, H> {
         return &mut self.cells;
     }
 
+    /// Returns the board's 3BV (Bechtel's Board Benchmark Value), the fewest reveals that clear it without flagging.
+    /// Each opening of empty cells takes one reveal, and so does each numbered cell that doesn't border an opening
+    pub fn three_bv(&self) -> usize {
+        let mut is_counted = [[false; W]; H];
+        let mut three_bv = 0;
+
+        // an opening is revealed all at once by the cascade, along with the numbers around it
+        for index in self.indices() {
+            let cell = self[index];
+            if cell.is_mine() || cell.local_mines() != 0 || is_counted[index.0][index.1] {
+                continue;
+            }
+            three_bv += 1;
+            let mut stack = vec![index];
+            while let Some(index) = stack.pop() {
+                if std::mem::replace(&mut is_counted[index.0][index.1], true) {
+                    continue;
+                }
+                if self[index].local_mines() == 0 {
+                    stack.extend(self.neighbors(index));
+                }
+            }
+        }
+
+        three_bv += self
+            .indices()
+            .filter(|&index| !self[index].is_mine() && !is_counted[index.0][index.1])
+            .count();
+        return three_bv;
+    }
+
     /// Returns a copy of all of the cells revealed
     pub fn clone_revealed(&self) -> Self {
-        let mut clone = Board::clone(&self);
+        let mut clone = Board::clone(self);
         for row in clone.cells_mut() {
             for cell in row {
                 cell.reveal();
@@ -156,3 +338,55 @@ impl<const W: usize, const H: usize> Board<W, H> {
         return clone;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbors_stay_on_the_board() {
+        let board: Board<3, 3> = Board::with_mines(&[]);
+        assert_eq!(board.neighbors((1, 1)).count(), 8);
+        assert_eq!(board.neighbors((0, 1)).count(), 5);
+        assert_eq!(
+            board.neighbors((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0), (1, 1)]
+        );
+        let board: Board<1, 1> = Board::with_mines(&[]);
+        assert_eq!(board.neighbors((0, 0)).count(), 0);
+    }
+
+    #[test]
+    fn local_mines_count_each_neighbor_once() {
+        let board: Board<2, 2> = Board::with_mines(&[(0, 1), (1, 0), (1, 1)]);
+        assert_eq!(board[(0, 0)].local_mines(), 3);
+    }
+
+    #[test]
+    fn reveal_cascades_up_to_numbers_and_flags() {
+        let mut board: Board<4, 1> = Board::with_mines(&[(0, 3)]);
+        assert_eq!(board.reveal((0, 0)), vec![(0, 0), (0, 1), (0, 2)]);
+        assert!(!board[(0, 3)].is_revealed());
+
+        let mut board: Board<4, 1> = Board::with_mines(&[]);
+        board[(0, 2)].flag();
+        assert_eq!(board.reveal((0, 0)), vec![(0, 0), (0, 1)]);
+        assert_eq!(board.reveal((9, 9)), vec![]);
+    }
+
+    #[test]
+    fn three_bv_counts_openings_and_lone_numbers() {
+        assert_eq!(Board::<3, 3>::with_mines(&[]).three_bv(), 1);
+        // every safe cell borders the mine, so each one is its own reveal
+        assert_eq!(Board::<3, 3>::with_mines(&[(1, 1)]).three_bv(), 8);
+        // one opening reveals the whole board
+        assert_eq!(Board::<5, 5>::with_mines(&[(0, 0)]).three_bv(), 1);
+        // two openings, split by a wall of mines
+        assert_eq!(
+            Board::<5, 3>::with_mines(&[(0, 2), (1, 2), (2, 2)]).three_bv(),
+            2
+        );
+        assert_eq!(Board::<3, 1>::with_mines(&[(0, 1)]).three_bv(), 2);
+        assert_eq!(Board::<2, 2>::with_mines(&[(0, 0), (1, 1)]).three_bv(), 2);
+    }
+}
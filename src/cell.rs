@@ -1,8 +1,7 @@
 use ::rand::{thread_rng, Rng};
-use macroquad::prelude::*;
 use std::fmt::Display;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 /// contains the information pertaining to a cell
 pub struct Cell {
     is_mine: bool,
@@ -30,6 +29,32 @@ impl Display for Cell {
         return Ok(());
     }
 }
+/// What a [Cell] turned out to be once the game is over, see [Cell::outcome]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// a flag on a mine
+    CorrectFlag,
+    /// a flag on a cell that isn't a mine
+    WrongFlag,
+    /// a mine that was neither flagged nor revealed
+    MissedMine,
+    /// a revealed mine, which ended the game
+    DetonatedMine,
+    /// a cell that isn't a mine, revealed or not
+    Safe { local_mines: usize },
+}
+impl Display for Outcome {
+    /// displays a correct flag as "⚑", a wrong flag as "X", a missed mine as "*", the detonated mine as "!" and a safe cell as its number
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            Outcome::CorrectFlag => write!(f, "⚑"),
+            Outcome::WrongFlag => write!(f, "X"),
+            Outcome::MissedMine => write!(f, "*"),
+            Outcome::DetonatedMine => write!(f, "!"),
+            Outcome::Safe { local_mines } => write!(f, "{}", local_mines),
+        };
+    }
+}
 impl Cell {
     /// a clear cell used for a default value
     pub const CLEAR: Self = Self {
@@ -41,9 +66,25 @@ impl Cell {
     pub fn local_mines(&self) -> usize {
         return self.local_mines;
     }
+    /// Classifies this [Cell] for showing the board once the game is over
+    pub fn outcome(&self) -> Outcome {
+        return match (self.is_mine, self.is_flagged, self.is_revealed) {
+            (true, _, true) => Outcome::DetonatedMine,
+            (true, true, false) => Outcome::CorrectFlag,
+            (true, false, false) => Outcome::MissedMine,
+            (false, true, _) => Outcome::WrongFlag,
+            (false, false, _) => Outcome::Safe {
+                local_mines: self.local_mines,
+            },
+        };
+    }
     /// used to generate a random cell
     pub fn random(is_mine_percentage: f64) -> Self {
         let mut rng = thread_rng(); // thread specific random number generator
+        return Self::random_with(is_mine_percentage, &mut rng);
+    }
+    /// used to generate a random cell from a specific random number generator, such as a seeded one
+    pub fn random_with(is_mine_percentage: f64, rng: &mut impl Rng) -> Self {
         return Self {
             is_mine: rng.gen_bool(is_mine_percentage),
             local_mines: 0,
@@ -78,13 +119,34 @@ impl Cell {
     pub fn set_local_mines(&mut self, local_mine_count: usize) {
         self.local_mines = local_mine_count;
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    pub fn color(&self) -> Color {
-        match (self.is_revealed, self.is_flagged, self.is_mine) {
-            (false, true, _) => GREEN,
-            (false, _, _) => GRAY,
-            (true, _, false) => LIGHTGRAY,
-            (true, _, true) => RED,
+    #[test]
+    fn every_cell_has_one_outcome() {
+        let safe = Outcome::Safe { local_mines: 3 };
+        // (is_mine, is_flagged, is_revealed)
+        let table = [
+            ((false, false, false), safe),
+            ((false, false, true), safe),
+            ((false, true, false), Outcome::WrongFlag),
+            ((false, true, true), Outcome::WrongFlag),
+            ((true, false, false), Outcome::MissedMine),
+            ((true, false, true), Outcome::DetonatedMine),
+            ((true, true, false), Outcome::CorrectFlag),
+            ((true, true, true), Outcome::DetonatedMine),
+        ];
+        for ((is_mine, is_flagged, is_revealed), outcome) in table {
+            let cell = Cell {
+                is_mine,
+                local_mines: 3,
+                is_revealed,
+                is_flagged,
+            };
+            assert_eq!(cell.outcome(), outcome, "{:?}", cell);
         }
     }
 }
@@ -0,0 +1,66 @@
+//! The traits a front end implements, and the game loop shared by every front end.
+//!
+//! A front end is a [Renderer] that shows the [Game] and a [CommandSource] that produces the player's [Command]s.
+//! New front ends only need to implement these traits, [Game] doesn't need to know about them.
+
+use crate::game::{Command, Event, Game, GameState};
+
+/// Shows a [Game] to the player
+pub trait Renderer {
+    type Error;
+    /// Draws the current view of the `game`
+    fn render<const W: usize, const H: usize>(
+        &mut self,
+        game: &Game<W, H>,
+    ) -> Result<(), Self::Error>;
+
+    /// Reacts to the [Event]s of the last [Command], like with a sound. Does nothing by default
+    fn react(&mut self, _events: Vec<Event>) -> Result<(), Self::Error> {
+        return Ok(());
+    }
+}
+
+/// Produces the player's [Command]s
+pub trait CommandSource {
+    type Error;
+    /// Returns the next [Command], or [None] if the player hasn't made one yet.
+    /// - Blocking sources, like a prompt, should always return a [Command]
+    /// - Polling sources, like a window, return [None] until the player acts
+    fn next_command<const W: usize, const H: usize>(
+        &mut self,
+        game: &Game<W, H>,
+    ) -> Result<Option<Command>, Self::Error>;
+}
+
+/// Plays one turn: renders the `game`, then applies the next [Command] from `source` if there is one
+/// and hands its [Event]s to the `renderer`. Front ends that own their loop, like macroquad's, call this once per frame.
+pub fn turn<const W: usize, const H: usize, R, S>(
+    game: &mut Game<W, H>,
+    renderer: &mut R,
+    source: &mut S,
+) -> Result<GameState, R::Error>
+where
+    R: Renderer,
+    S: CommandSource<Error = R::Error>,
+{
+    renderer.render(game)?;
+    if let Some(command) = source.next_command(game)? {
+        game.apply(command);
+        renderer.react(game.take_events())?;
+    }
+    return Ok(game.state());
+}
+
+/// Plays `game` with [turn] until a [Command::Quit]
+pub fn run<const W: usize, const H: usize, R, S>(
+    mut game: Game<W, H>,
+    renderer: &mut R,
+    source: &mut S,
+) -> Result<(), R::Error>
+where
+    R: Renderer,
+    S: CommandSource<Error = R::Error>,
+{
+    while !matches!(turn(&mut game, renderer, source)?, GameState::Quit) {}
+    return Ok(());
+}
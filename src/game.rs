@@ -1,246 +1,558 @@
-use std::io::{stdout, Write};
+use std::{
+    fmt::Display,
+    str::FromStr,
+    time::{Duration, Instant},
+};
 
-use macroquad::{miniquad::window::screen_size, prelude::*};
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
 
-use crate::{
-    board::Board,
-    input::{clear_terminal, get_parsed_input, quit_terminal, Action},
-};
+use crate::board::Board;
 
 pub const WIDTH: usize = 32;
 pub const HEIGHT: usize = WIDTH;
-pub const SCALE_FACTOR: f32 = WIDTH as f32 * 0.0008;
 
 pub const EASY_MINES: f64 = 0.0625;
 pub const MEDIUM_MINES: f64 = 0.125;
 pub const HARD_MINES: f64 = 0.25;
 
-#[derive(Debug, Clone, Copy)]
-pub struct Game {
-    board: Board<WIDTH, HEIGHT>,
+/// The rules of minesweeper on a `W`x`H` [Board]. Front ends drive a game by [applying](Game::apply) [Command]s
+#[derive(Debug, Clone)]
+pub struct Game<const W: usize, const H: usize> {
+    board: Board<W, H>,
     state: GameState,
     cell_index: (usize, usize),
     action: Action,
+    is_mine_percentage: f64,
+    /// generates every [Board], so a seeded game always deals the same sequence of boards
+    rng: StdRng,
+    /// when the first cell was revealed
+    started: Option<Instant>,
+    /// when the game ended
+    finished: Option<Instant>,
+    /// the [Board] before each move, most recent last
+    history: Vec<Board<W, H>>,
+    /// a safe cell found by [Command::Hint]
+    hint: Option<(usize, usize)>,
+    /// the cell the last move changed
+    last_move: Option<(usize, usize)>,
+    /// what happened since the front end last [took](Game::take_events) them
+    events: Vec<Event>,
+}
+impl<const W: usize, const H: usize> Default for Game<W, H> {
+    fn default() -> Self {
+        return Self::new(MEDIUM_MINES);
+    }
 }
-impl Game {
-    pub fn new() -> Self {
+impl<const W: usize, const H: usize> Game<W, H> {
+    /// Starts a game where each cell has a `is_mine_percentage` chance of being a mine
+    pub fn new(is_mine_percentage: f64) -> Self {
+        return Self::seeded(is_mine_percentage, thread_rng().gen());
+    }
+
+    /// Starts a game whose boards are generated from `seed`
+    pub fn seeded(is_mine_percentage: f64, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
         return Self {
-            board: Board::random(MEDIUM_MINES),
+            board: Board::random_with(is_mine_percentage, &mut rng),
             state: GameState::Playing,
             cell_index: (0, 0),
             action: Action::Cancel,
+            is_mine_percentage,
+            rng,
+            started: None,
+            finished: None,
+            history: Vec::new(),
+            hint: None,
+            last_move: None,
+            events: Vec::new(),
         };
     }
 
-    /// Only to be called in execute_turn. must be called fourth
+    /// Replaces the board with the next one from this game's random number generator
+    pub fn restart(&mut self) {
+        self.board = Board::random_with(self.is_mine_percentage, &mut self.rng);
+        self.state = GameState::Playing;
+        self.cell_index = (0, 0);
+        self.action = Action::Cancel;
+        self.started = None;
+        self.finished = None;
+        self.history.clear();
+        self.hint = None;
+        self.last_move = None;
+        self.events.clear();
+    }
+
+    /// Applies the selected [Action] to the selected cell. Does nothing unless the game is being played
     pub fn execute_action(&mut self) {
-        match self.action {
-            Action::Reveal => {
-                self.board[self.cell_index].reveal();
-                if self.board[self.cell_index].is_mine() {
-                    self.state = GameState::GameOver;
+        if !matches!(self.state, GameState::Playing) {
+            return;
+        }
+        let cell = self.board[self.cell_index];
+        let event = match self.action {
+            Action::Reveal => match self.reveal(&[self.cell_index]) {
+                0 => None,
+                1 => Some(Event::Reveal),
+                _ => Some(Event::Cascade),
+            },
+            Action::Flag => {
+                if cell.is_revealed() || cell.is_flagged() {
+                    None
+                } else {
+                    self.board[self.cell_index].flag();
+                    Some(Event::Flag)
+                }
+            }
+            Action::Unflag => {
+                if cell.is_flagged() {
+                    self.board[self.cell_index].unflag();
+                    Some(Event::Unflag)
+                } else {
+                    None
+                }
+            }
+            Action::Chord => {
+                if cell.is_revealed()
+                    && cell.local_mines() == self.board.neighbor_flags(self.cell_index)
+                {
+                    let hidden_neighbors: Vec<_> =
+                        self.board.hidden_neighbors(self.cell_index).collect();
+                    (self.reveal(&hidden_neighbors) > 0).then_some(Event::Chord)
+                } else {
+                    None
                 }
             }
-            Action::Flag => self.board[self.cell_index].flag(),
-            Action::Unflag => self.board[self.cell_index].unflag(),
-            Action::Cancel => (),
+            Action::Cancel => None,
         };
-    }
 
-    pub fn set_action(&mut self, action: Action) {
-        self.action = action;
-    }
-
-    pub fn set_cell_index(&mut self, cell_index: (usize, usize)) {
-        if self.board.get_cell(cell_index).is_some() {
-            self.cell_index = cell_index;
-        }
-    }
-}
-/// macroquad methods
-impl Game {
-    pub fn update(&mut self) -> GameState {
-        if let GameState::Playing = self.state {
-            self.handle_input();
-            self.execute_action();
+        // a move that ends the game is heard as the end of the game alone
+        let event = match self.state {
+            GameState::GameOver => Some(Event::Explosion),
+            GameState::Won => Some(Event::Victory),
+            GameState::Playing | GameState::Quit => event,
         };
-        return self.state;
-    }
-
-    fn cell_size() -> f32 {
-        screen_width().min(screen_height()) * SCALE_FACTOR
+        self.events.extend(event);
     }
 
-    fn cell_boundaries() -> [[Rect; WIDTH]; HEIGHT] {
-        let mut boundaries = [[Rect::default(); WIDTH]; HEIGHT];
-
-        let cell_size: f32 = Self::cell_size();
-
-        let window_origin = Vec2::from(screen_size()) / 2.0;
-
-        let board_position =
-            window_origin - (Vec2::new(WIDTH as f32, HEIGHT as f32) * cell_size) / 2.0;
-
-        for row_index in 0..HEIGHT {
-            for column_index in 0..WIDTH {
-                
-                let cell_position =
-                    board_position + (cell_size * Vec2::new(row_index as f32, column_index as f32));
-
-                let cell_boundary =
-                    Rect::new(cell_position.x, cell_position.y, cell_size, cell_size);
-
-                boundaries[row_index][column_index] = cell_boundary;
+    /// Reveals each cell in `indices`, cascading from empty cells. Revealing a mine ends the game, revealing every other cell wins it.
+    /// Returns how many cells were revealed
+    fn reveal(&mut self, indices: &[(usize, usize)]) -> usize {
+        self.started.get_or_insert_with(Instant::now);
+        let mut count = 0;
+        for &index in indices {
+            let revealed = self.board.reveal(index);
+            count += revealed.len();
+            if revealed.iter().any(|&index| self.board[index].is_mine()) {
+                self.state = GameState::GameOver;
+                self.finished = Some(Instant::now());
             }
         }
-
-        return boundaries;
-    }
-
-    pub fn draw(&mut self) {
-        let cell_boundaries = Self::cell_boundaries();
-        let cell_size: f32 = Self::cell_size();
-        let border_size = cell_size * 0.05;
-
-        for (row_index, row) in cell_boundaries.into_iter().enumerate() {
-            for (column_index, boundary) in row.into_iter().enumerate() {
-                let color = self
-                    .board
-                    .get_cell((row_index, column_index))
-                    .and_then(|cell| Some(cell.color()))
-                    .unwrap_or(WHITE);
-
-                // draw border
-                draw_rectangle(boundary.x, boundary.y, boundary.w, boundary.h, BLACK);
-
-                // draw interior
-                draw_rectangle(
-                    boundary.x + border_size,
-                    boundary.y + border_size,
-                    boundary.w - 2.0 * border_size,
-                    boundary.h - 2.0 * border_size,
-                    color,
-                );
-            }
+        if matches!(self.state, GameState::Playing) && self.board.is_cleared() {
+            self.state = GameState::Won;
+            self.finished = Some(Instant::now());
         }
+        return count;
     }
 
-    fn handle_input(&mut self) {
-        let mut click = false;
-        
-        if is_mouse_button_released(MouseButton::Left) {
-            self.action = Action::Reveal;
-            click = true;
-        }
-        if is_mouse_button_released(MouseButton::Right) {
-            match self.action {
-                Action::Flag => self.action = Action::Unflag,
-                Action::Unflag => self.action = Action::Flag,
-                _ => {},
-            };
-            click = true;
-        }
-
-        if click {
-
-            for (row_index, row) in Self::cell_boundaries().into_iter().enumerate() {
-                for (column_index, boundary) in row.into_iter().enumerate() {
-                    
-                    if boundary.contains(mouse_position().into()) {
-                        
-                        if let Some(cell) = self.board.get_cell_mut((row_index, column_index)) {
-                            self.set_cell_index((row_index, column_index));
-                            self.execute_action();                      
-                        }
-
-                        break;
-                    }
+    /// Applies a [Command] from a front end. Acting on a cell that isn't on the board does nothing
+    pub fn apply(&mut self, command: Command) {
+        match command {
+            Command::Act { action, cell_index } => {
+                if self.board.get_cell(cell_index).is_none() {
+                    return;
+                }
+                self.set_cell_index(cell_index);
+                self.set_action(action);
+
+                let before = self.board;
+                self.execute_action();
+                if self.board != before {
+                    self.history.push(before);
+                    self.hint = None;
+                    self.last_move = Some(self.cell_index);
                 }
             }
-        }
+            Command::Undo => self.undo(),
+            Command::Hint => {
+                self.hint = self.find_hint();
+                if let Some(hint) = self.hint {
+                    self.cell_index = hint;
+                }
+            }
+            Command::Select { cell_index } => self.set_cell_index(cell_index),
+            Command::NewGame => self.restart(),
+            Command::Quit => self.state = GameState::Quit,
+        };
     }
 
-    fn handle_game_over(&mut self) {
-
-    }
-}
-/// Terminal methods
-impl Game {
-    pub fn update_terminal(&mut self) -> Result<GameState, std::io::Error> {
-        if let GameState::Playing = self.state {
-            self.print_board()?;
-            self.get_cell_index_terminal()?;
-            self.get_action_terminal()?;
-            self.execute_action();
-            self.handle_game_over_terminal()?;
+    /// Restores the [Board] from before the last move. Undoing the move that revealed a mine resumes the game
+    pub fn undo(&mut self) {
+        if let Some(board) = self.history.pop() {
+            self.board = board;
+            self.state = GameState::Playing;
+            self.finished = None;
+            self.hint = None;
+            self.last_move = None;
         }
-        return Ok(self.state);
     }
 
-    /// Only to be called in execute_turn. must be called first
-    fn print_board(&self) -> Result<(), std::io::Error> {
-        clear_terminal()?;
-        writeln!(stdout(), "{}\n", self.board)?;
-        return Ok(());
+    /// Returns a hidden cell that isn't a mine. Cells next to revealed cells are preferred, then cells with fewer local mines
+    pub fn find_hint(&self) -> Option<(usize, usize)> {
+        let is_safe = |index: (usize, usize)| {
+            let cell = self.board[index];
+            return !cell.is_revealed() && !cell.is_flagged() && !cell.is_mine();
+        };
+        let borders_revealed = |index: (usize, usize)| {
+            return self
+                .board
+                .neighbors(index)
+                .any(|neighbor| self.board[neighbor].is_revealed());
+        };
+        return self
+            .board
+            .indices()
+            .filter(|&index| is_safe(index))
+            .min_by_key(|&index| (!borders_revealed(index), self.board[index].local_mines()));
     }
 
-    /// Only to be called in execute_turn. must be called second
-    fn get_cell_index_terminal(&mut self) -> Result<(), std::io::Error> {
-        loop {
-            // allow user to select a cell
-            let row_index = get_parsed_input("Select a cell\nPlease enter a row number: ")?;
-            let column_index = get_parsed_input("Please enter a column number: ")?;
-
-            // ensure the user entered a valid cell
-            if self.board.get_cell((row_index, column_index)).is_some() {
-                self.cell_index = (row_index, column_index);
-                return Ok(());
-            }
+    pub fn set_action(&mut self, action: Action) {
+        self.action = action;
+    }
 
-            println!(
-                "\nThat cell is out of {}x{} bounds. Try again\n",
-                self.board.width(),
-                self.board.height()
-            );
+    /// Selects the cell at `cell_index` if it is on the board
+    pub fn set_cell_index(&mut self, cell_index: (usize, usize)) {
+        if self.board.get_cell(cell_index).is_some() {
+            self.cell_index = cell_index;
         }
     }
 
-    /// Only to be called in execute_turn. must be called third
-    pub fn get_action_terminal(&mut self) -> Result<(), std::io::Error> {
-        self.action =
-            get_parsed_input("\nSelect an action for this cell\nReveal\nFlag\nUnflag\nCancel\n")?;
-        return Ok(());
-    }
-
-    pub fn handle_game_over_terminal(&mut self) -> Result<(), std::io::Error> {
-        clear_terminal()?;
-        writeln!(
-            stdout(),
-            "YOU REVEALED A MINE\nGAME OVER\n{}",
-            self.board.clone_revealed()
-        )?;
-        self.state = if quit_terminal()? {
-            GameState::Quit
-        } else {
-            GameState::Playing
+    /// returns a reference to the [Board] being played on
+    pub fn board(&self) -> &Board<W, H> {
+        return &self.board;
+    }
+    /// returns the current [GameState]
+    pub fn state(&self) -> GameState {
+        return self.state;
+    }
+    /// returns the index of the selected cell
+    pub fn cell_index(&self) -> (usize, usize) {
+        return self.cell_index;
+    }
+    /// returns the selected [Action]
+    pub fn action(&self) -> Action {
+        return self.action;
+    }
+    /// returns the cell the last move changed
+    pub fn last_move(&self) -> Option<(usize, usize)> {
+        return self.last_move;
+    }
+    /// Returns the [Event]s since the last call, oldest first. [frontend::turn](crate::frontend::turn) takes them after every [Command]
+    pub fn take_events(&mut self) -> Vec<Event> {
+        return std::mem::take(&mut self.events);
+    }
+    /// returns the safe cell found by the last [Command::Hint], until the next move
+    pub fn hint(&self) -> Option<(usize, usize)> {
+        return self.hint;
+    }
+    /// returns how long the game has been played for. The clock starts at the first reveal and stops when the game ends
+    pub fn elapsed(&self) -> Duration {
+        return match (self.started, self.finished) {
+            (Some(started), Some(finished)) => finished - started,
+            (Some(started), None) => started.elapsed(),
+            (None, _) => Duration::ZERO,
+        };
+    }
+    /// returns the number of mines minus the number of flags. Negative when there are more flags than mines
+    pub fn remaining_mines(&self) -> isize {
+        return self.board.mine_count() as isize - self.board.flag_count() as isize;
+    }
+}
+
+/// The preset board sizes and mine densities
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Difficulty {
+    /// 9x9 board with [EASY_MINES]
+    Beginner,
+    /// 16x16 board with [MEDIUM_MINES]
+    Intermediate,
+    /// 30x16 board with [HARD_MINES]
+    Expert,
+    /// [WIDTH]x[HEIGHT] board with [MEDIUM_MINES]
+    #[default]
+    Large,
+}
+impl Difficulty {
+    /// Every [Difficulty], in order of board size
+    pub const ALL: [Self; 4] = [
+        Difficulty::Beginner,
+        Difficulty::Intermediate,
+        Difficulty::Expert,
+        Difficulty::Large,
+    ];
+    /// The number of columns on the board
+    pub const fn width(&self) -> usize {
+        return match self {
+            Difficulty::Beginner => 9,
+            Difficulty::Intermediate => 16,
+            Difficulty::Expert => 30,
+            Difficulty::Large => WIDTH,
+        };
+    }
+    /// The number of rows on the board
+    pub const fn height(&self) -> usize {
+        return match self {
+            Difficulty::Beginner => 9,
+            Difficulty::Intermediate => 16,
+            Difficulty::Expert => 16,
+            Difficulty::Large => HEIGHT,
+        };
+    }
+    /// The chance of each cell being a mine
+    pub const fn is_mine_percentage(&self) -> f64 {
+        return match self {
+            Difficulty::Beginner => EASY_MINES,
+            Difficulty::Intermediate => MEDIUM_MINES,
+            Difficulty::Expert => HARD_MINES,
+            Difficulty::Large => MEDIUM_MINES,
+        };
+    }
+}
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Difficulty::Beginner => "beginner",
+            Difficulty::Intermediate => "intermediate",
+            Difficulty::Expert => "expert",
+            Difficulty::Large => "large",
+        };
+        return write!(f, "{}", name);
+    }
+}
+impl FromStr for Difficulty {
+    type Err = Box<dyn std::error::Error>;
+    /// Parses the name of a [Difficulty], or its first letter
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return match s.to_lowercase().as_str() {
+            "b" | "beginner" | "easy" => Ok(Difficulty::Beginner),
+            "i" | "intermediate" | "medium" => Ok(Difficulty::Intermediate),
+            "e" | "expert" | "hard" => Ok(Difficulty::Expert),
+            "l" | "large" => Ok(Difficulty::Large),
+            invalid => Err(format!(
+                "{} is not a difficulty. Choose beginner, intermediate, expert or large",
+                invalid
+            )
+            .into()),
         };
-        return Ok(());
     }
 }
 
 /// A marker type to signify a game over
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GameState {
+    /// a mine was revealed
     GameOver,
+    /// every cell that isn't a mine was revealed
+    Won,
     Playing,
     Quit,
 }
 impl GameState {
     pub fn is_game_over(&self) -> bool {
-        return if let GameState::GameOver = self {
-            true
-        } else {
-            false
+        return matches!(self, GameState::GameOver);
+    }
+    pub fn is_won(&self) -> bool {
+        return matches!(self, GameState::Won);
+    }
+    /// Returns true when the game was lost or won
+    pub fn is_finished(&self) -> bool {
+        return self.is_game_over() || self.is_won();
+    }
+}
+
+/// A moment in a [Game] that front ends can react to, like with a sound. Collected with [Game::take_events]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Event {
+    /// one cell was revealed
+    Reveal,
+    /// revealing an empty cell revealed its neighbors too
+    Cascade,
+    Flag,
+    Unflag,
+    /// a chord revealed at least one cell
+    Chord,
+    /// a mine was revealed, ending the game
+    Explosion,
+    /// the last safe cell was revealed
+    Victory,
+}
+impl Event {
+    pub const ALL: [Event; 7] = [
+        Event::Reveal,
+        Event::Cascade,
+        Event::Flag,
+        Event::Unflag,
+        Event::Chord,
+        Event::Explosion,
+        Event::Victory,
+    ];
+}
+impl Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Event::Reveal => "reveal",
+            Event::Cascade => "cascade",
+            Event::Flag => "flag",
+            Event::Unflag => "unflag",
+            Event::Chord => "chord",
+            Event::Explosion => "explosion",
+            Event::Victory => "victory",
+        };
+        return write!(f, "{}", name);
+    }
+}
+
+/// A request from the player, produced by a [CommandSource](crate::frontend::CommandSource)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// Apply an [Action] to the cell at `cell_index`
+    Act {
+        action: Action,
+        cell_index: (usize, usize),
+    },
+    /// Select the cell at `cell_index` without acting on it, like moving a cursor
+    Select { cell_index: (usize, usize) },
+    /// Take back the last move
+    Undo,
+    /// Find and select a hidden cell that isn't a mine
+    Hint,
+    /// Replace the game with a new one
+    NewGame,
+    /// Stop playing
+    Quit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// This enum represents the possible actions a user can take after selecting a [Cell](crate::cell::Cell)
+pub enum Action {
+    Reveal,
+    Flag,
+    Unflag,
+    /// Reveal the hidden neighbors of a revealed cell whose local mines are all flagged
+    Chord,
+    Cancel,
+}
+impl Action {
+    pub fn is_reveal(&self) -> bool {
+        return matches!(self, Action::Reveal);
+    }
+    pub fn is_flag(&self) -> bool {
+        return matches!(self, Action::Flag);
+    }
+    pub fn is_unflag(&self) -> bool {
+        return matches!(self, Action::Unflag);
+    }
+    pub fn is_chord(&self) -> bool {
+        return matches!(self, Action::Chord);
+    }
+    pub fn is_cancel(&self) -> bool {
+        return matches!(self, Action::Cancel);
+    }
+}
+impl FromStr for Action {
+    type Err = Box<dyn std::error::Error>;
+    /// Defines how a Action is parsed from a string
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return match s.to_lowercase().as_str() {
+            "r" | "reveal" => Ok(Action::Reveal),
+            "f" | "flag" => Ok(Action::Flag),
+            "u" | "unflag" => Ok(Action::Unflag),
+            "c" | "chord" => Ok(Action::Chord),
+            "cancel" => Ok(Action::Cancel),
+            invalid => Err(format!("{} is not a valid cell action.\n either use the first letter or type the whole action", invalid).into()),
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A game on a board with mines at `mines` and every other cell clear
+    fn with_mines<const W: usize, const H: usize>(mines: &[(usize, usize)]) -> Game<W, H> {
+        let mut game = Game::seeded(0.0, 0);
+        game.board = Board::with_mines(mines);
+        return game;
+    }
+
+    fn act(action: Action, cell_index: (usize, usize)) -> Command {
+        return Command::Act { action, cell_index };
+    }
+
+    #[test]
+    fn acting_off_the_board_does_nothing() {
+        let mut game: Game<9, 9> = Game::seeded(0.2, 1);
+        let board = *game.board();
+        game.apply(Command::Act {
+            action: Action::Reveal,
+            cell_index: (100, 100),
+        });
+        assert_eq!(*game.board(), board);
+        assert_eq!(game.cell_index(), (0, 0));
+        assert_eq!(game.state(), GameState::Playing);
+    }
+
+    #[test]
+    fn a_move_that_ends_the_game_is_heard_as_the_end_alone() {
+        let mut game: Game<4, 1> = with_mines(&[(0, 0)]);
+        game.apply(act(Action::Reveal, (0, 1)));
+        assert_eq!(game.take_events(), vec![Event::Reveal]);
+        game.apply(act(Action::Reveal, (0, 0)));
+        assert_eq!(game.take_events(), vec![Event::Explosion]);
+
+        let mut game: Game<4, 1> = with_mines(&[(0, 0)]);
+        game.apply(act(Action::Reveal, (0, 3)));
+        assert_eq!(game.state(), GameState::Won);
+        assert_eq!(game.take_events(), vec![Event::Victory]);
+    }
+
+    #[test]
+    fn a_chord_reveals_the_neighbors_once_the_flags_match() {
+        // (1, 1) touches both mines
+        let mut game: Game<4, 3> = with_mines(&[(0, 0), (2, 2)]);
+        game.apply(act(Action::Reveal, (1, 1)));
+        game.apply(act(Action::Flag, (0, 0)));
+
+        let board = *game.board();
+        game.apply(act(Action::Chord, (1, 1)));
+        assert_eq!(*game.board(), board, "one flag for two mines");
+
+        game.apply(act(Action::Flag, (2, 2)));
+        game.apply(act(Action::Chord, (1, 1)));
+        for index in [(0, 1), (0, 2), (1, 0), (1, 2), (2, 0), (2, 1)] {
+            assert!(game.board()[index].is_revealed(), "{:?}", index);
+        }
+        assert_eq!(game.state(), GameState::Playing);
+    }
+
+    #[test]
+    fn a_chord_onto_a_wrong_flag_ends_the_game() {
+        let mut game: Game<3, 3> = with_mines(&[(0, 0)]);
+        game.apply(act(Action::Reveal, (1, 1)));
+        game.apply(act(Action::Flag, (0, 1)));
+        game.apply(act(Action::Chord, (1, 1)));
+        assert!(game.board()[(0, 0)].is_revealed());
+        assert_eq!(game.state(), GameState::GameOver);
+    }
+
+    #[test]
+    fn revealing_the_last_safe_cell_wins() {
+        let mut game: Game<4, 3> = with_mines(&[(0, 0), (2, 2)]);
+        game.apply(act(Action::Reveal, (0, 3)));
+        game.apply(act(Action::Reveal, (1, 1)));
+        for index in [(0, 1), (1, 0), (2, 0), (2, 1)] {
+            game.apply(act(Action::Reveal, index));
+            assert_eq!(game.state(), GameState::Playing);
+        }
+        game.apply(act(Action::Reveal, (2, 3)));
+        assert_eq!(game.state(), GameState::Won);
+    }
+}
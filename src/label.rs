@@ -0,0 +1,102 @@
+//! Spreadsheet style cell labels, like `C7` or `AA12`.
+//!
+//! Columns are lettered `A` to `Z`, then `AA`, `AB` and so on. Rows are numbered from 1, like a spreadsheet,
+//! so they are one more than the [Board](crate::board::Board)'s row indices. `C7` is the [Cell](crate::cell::Cell) at index `(6, 2)`.
+
+/// Returns the letters for the column at `column_index`. 0 is `A`, 25 is `Z` and 26 is `AA`
+pub fn column_label(column_index: usize) -> String {
+    let mut letters = Vec::new();
+    let mut remaining = column_index;
+    loop {
+        letters.push((b'A' + (remaining % 26) as u8) as char);
+        if remaining < 26 {
+            break;
+        }
+        remaining = remaining / 26 - 1;
+    }
+    return letters.into_iter().rev().collect();
+}
+
+/// Returns the number for the row at `row_index`. 0 is `1`
+pub fn row_label(row_index: usize) -> String {
+    return (row_index + 1).to_string();
+}
+
+/// Returns the label of the cell at `(row_index, column_index)`, like `C7`
+pub fn cell_label(index: (usize, usize)) -> String {
+    return format!("{}{}", column_label(index.1), row_label(index.0));
+}
+
+/// Parses column letters into a column index. Not case sensitive. Returns [None] if `letters` isn't only letters
+pub fn parse_column_label(letters: &str) -> Option<usize> {
+    if letters.is_empty() {
+        return None;
+    }
+    let column_number = letters.chars().try_fold(0_usize, |column_number, letter| {
+        if !letter.is_ascii_alphabetic() {
+            return None;
+        }
+        let letter_value = (letter.to_ascii_uppercase() as u8 - b'A') as usize + 1;
+        return column_number.checked_mul(26)?.checked_add(letter_value);
+    })?;
+    return Some(column_number - 1);
+}
+
+/// Parses a row number into a row index. Returns [None] if `digits` isn't a number from 1
+pub fn parse_row_label(digits: &str) -> Option<usize> {
+    if !digits.chars().all(|character| character.is_ascii_digit()) {
+        return None;
+    }
+    return digits.parse::<usize>().ok()?.checked_sub(1);
+}
+
+/// Parses a cell label like `C7` into a `(row_index, column_index)`. Not case sensitive
+pub fn parse_cell_label(label: &str) -> Option<(usize, usize)> {
+    let digits_start = label.find(|character: char| !character.is_ascii_alphabetic())?;
+    let (letters, digits) = label.split_at(digits_start);
+    return Some((parse_row_label(digits)?, parse_column_label(letters)?));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn columns_are_lettered_like_a_spreadsheet() {
+        assert_eq!(column_label(0), "A");
+        assert_eq!(column_label(25), "Z");
+        assert_eq!(column_label(26), "AA");
+        assert_eq!(column_label(701), "ZZ");
+        assert_eq!(column_label(702), "AAA");
+        for column_index in 0..2000 {
+            assert_eq!(
+                parse_column_label(&column_label(column_index)),
+                Some(column_index)
+            );
+        }
+    }
+
+    #[test]
+    fn rows_are_numbered_from_one() {
+        assert_eq!(cell_label((0, 0)), "A1");
+        assert_eq!(cell_label((6, 2)), "C7");
+        assert_eq!(cell_label((11, 26)), "AA12");
+        assert_eq!(parse_cell_label("C7"), Some((6, 2)));
+        assert_eq!(parse_cell_label("aa12"), Some((11, 26)));
+        assert_eq!(parse_cell_label("A0"), None);
+    }
+
+    #[test]
+    fn malformed_labels_are_rejected() {
+        for label in ["", "C", "7", "7C", "C7x", "C-7", "C 7", "É7"] {
+            assert_eq!(parse_cell_label(label), None, "{}", label);
+        }
+    }
+
+    #[test]
+    fn overflowing_labels_are_rejected() {
+        assert_eq!(parse_column_label(&"Z".repeat(20)), None);
+        assert_eq!(parse_cell_label(&format!("{}1", "Z".repeat(20))), None);
+        assert_eq!(parse_cell_label("A99999999999999999999999"), None);
+    }
+}
@@ -0,0 +1,243 @@
+//! Lays out a board as a text grid with [labelled](crate::label) columns and numbered rows.
+//!
+//! The labels and cells are padded to the widest label, so the grid stays aligned for any board size.
+
+use std::{fmt::Write, ops::Range};
+
+use crate::label::{column_label, row_label};
+
+/// The lines drawn around the grid
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Border {
+    /// A `-` under the column labels and a `|` after the row labels
+    #[default]
+    Ascii,
+    /// A box drawn with box-drawing characters, like `┌─┐`
+    Box,
+}
+
+/// The widths of each part of a text grid of `columns`x`rows` cells, and which of them are shown
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridLayout {
+    /// the columns that are shown
+    columns: Range<usize>,
+    /// the rows that are shown
+    rows: Range<usize>,
+    /// the width of every cell, at least as wide as the widest column label
+    column_width: usize,
+    /// the width of the widest row number
+    row_label_width: usize,
+    border: Border,
+}
+impl GridLayout {
+    /// Lays out a grid with `columns` columns and `rows` rows whose cells are at most `cell_width` wide
+    pub fn new(columns: usize, rows: usize, cell_width: usize, border: Border) -> Self {
+        return Self {
+            columns: 0..columns,
+            rows: 0..rows,
+            column_width: column_label(columns.saturating_sub(1))
+                .len()
+                .max(cell_width),
+            row_label_width: row_label(rows.saturating_sub(1)).len(),
+            border,
+        };
+    }
+
+    /// Only shows the cells in `rows` and `columns`. The widths stay those of the whole grid, so the layout doesn't shift while scrolling
+    pub fn window(mut self, rows: Range<usize>, columns: Range<usize>) -> Self {
+        self.rows = rows.start.min(self.rows.end)..rows.end.min(self.rows.end);
+        self.columns = columns.start.min(self.columns.end)..columns.end.min(self.columns.end);
+        return self;
+    }
+
+    /// Returns how many `(rows, columns)` fit in `width`x`height` characters, including the labels and border
+    pub fn fit(&self, width: usize, height: usize) -> (usize, usize) {
+        let border_width = match self.border {
+            Border::Ascii => 0,
+            Border::Box => 1,
+        };
+        let border_height = match self.border {
+            Border::Ascii => 1,
+            Border::Box => 2,
+        };
+        // one line of column labels
+        let rows = height.saturating_sub(1 + border_height);
+        let columns =
+            width.saturating_sub(self.row_prefix_width() + border_width) / (self.column_width + 1);
+        return (rows.max(1), columns.max(1));
+    }
+
+    /// The number of characters before the first cell of a row
+    fn row_prefix_width(&self) -> usize {
+        return match self.border {
+            // "12 |"
+            Border::Ascii => self.row_label_width + 2,
+            // "12 │ "
+            Border::Box => self.row_label_width + 3,
+        };
+    }
+
+    /// The number of characters taken by the cells of a row. Each cell is followed by a space
+    fn cells_width(&self) -> usize {
+        return self.columns.len() * (self.column_width + 1);
+    }
+
+    /// Writes the grid to `f`.
+    /// - `cell_text` returns the text of the cell at an index. The text is padded to the column width
+    /// - `style_cell` is given each cell's index and padded text, and returns the text to write. This lets renderers add escape codes without breaking the alignment
+    pub fn write(
+        &self,
+        f: &mut impl Write,
+        mut cell_text: impl FnMut((usize, usize)) -> String,
+        mut style_cell: impl FnMut((usize, usize), String) -> String,
+    ) -> std::fmt::Result {
+        let column_width = self.column_width;
+        let row_label_width = self.row_label_width;
+
+        // column labels
+        write!(f, "{}", " ".repeat(self.row_prefix_width()))?;
+        for column_index in self.columns.clone() {
+            write!(f, "{:<column_width$} ", column_label(column_index))?;
+        }
+        // as wide as the right side of the box below
+        if let Border::Box = self.border {
+            write!(f, " ")?;
+        }
+        writeln!(f)?;
+
+        match self.border {
+            Border::Ascii => writeln!(
+                f,
+                "{}{}",
+                " ".repeat(self.row_prefix_width()),
+                "-".repeat(self.cells_width())
+            )?,
+            Border::Box => writeln!(
+                f,
+                "{} ┌{}┐",
+                " ".repeat(row_label_width),
+                "─".repeat(self.cells_width() + 1)
+            )?,
+        }
+
+        for row_index in self.rows.clone() {
+            match self.border {
+                Border::Ascii => write!(f, "{:>row_label_width$} |", row_label(row_index))?,
+                Border::Box => write!(f, "{:>row_label_width$} │ ", row_label(row_index))?,
+            }
+            for column_index in self.columns.clone() {
+                let index = (row_index, column_index);
+                let text = format!("{:<column_width$}", cell_text(index));
+                write!(f, "{} ", style_cell(index, text))?;
+            }
+            match self.border {
+                Border::Ascii => writeln!(f)?,
+                Border::Box => writeln!(f, "│")?,
+            }
+        }
+
+        if let Border::Box = self.border {
+            writeln!(
+                f,
+                "{} └{}┘",
+                " ".repeat(row_label_width),
+                "─".repeat(self.cells_width() + 1)
+            )?;
+        }
+
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+
+    /// The lines of `board` written with `layout`
+    fn lines<const W: usize, const H: usize>(
+        board: &Board<W, H>,
+        layout: &GridLayout,
+    ) -> Vec<String> {
+        let mut text = String::new();
+        layout
+            .write(&mut text, |index| board[index].to_string(), |_, text| text)
+            .unwrap();
+        return text.lines().map(String::from).collect();
+    }
+
+    #[test]
+    fn every_line_is_as_wide_and_the_labels_are_over_their_cells() {
+        let board: Board<100, 100> = Board::with_mines(&[(0, 0), (99, 99)]);
+        for border in [Border::Ascii, Border::Box] {
+            let layout = board.layout(border);
+            let lines = lines(&board, &layout);
+            let width = lines[0].chars().count();
+            assert!(
+                lines.iter().all(|line| line.chars().count() == width),
+                "{:?}",
+                border
+            );
+
+            let header: Vec<char> = lines[0].chars().collect();
+            for row_index in [0, 9, 99] {
+                let row: Vec<char> = lines[row_index + 2].chars().collect();
+                for column_index in [0, 25, 99] {
+                    let start =
+                        layout.row_prefix_width() + column_index * (layout.column_width + 1);
+                    let label = column_label(column_index);
+                    let under_label: String = header[start..start + label.len()].iter().collect();
+                    assert_eq!(under_label, label, "{:?}", border);
+                    assert_eq!(
+                        row[start].to_string(),
+                        board[(row_index, column_index)].to_string(),
+                        "{:?} {}",
+                        border,
+                        label
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn a_window_keeps_the_whole_grids_widths() {
+        let layout = GridLayout::new(100, 100, 1, Border::Ascii);
+        let window = layout.clone().window(10..20, 30..40);
+        assert_eq!(window.rows, 10..20);
+        assert_eq!(window.columns, 30..40);
+        assert_eq!(window.column_width, layout.column_width);
+        assert_eq!(window.row_label_width, layout.row_label_width);
+
+        // a window past the edge is cut to the grid
+        let window = layout.window(90..120, 120..130);
+        assert_eq!(window.rows, 90..100);
+        assert_eq!(window.columns, 100..100);
+    }
+
+    #[test]
+    fn a_fitted_window_fits() {
+        let board: Board<100, 100> = Board::with_mines(&[]);
+        for border in [Border::Ascii, Border::Box] {
+            for (width, height) in [(80, 24), (120, 40), (10, 5)] {
+                let layout = board.layout(border);
+                let (rows, columns) = layout.fit(width, height);
+                let lines = lines(&board, &layout.window(0..rows, 0..columns));
+                assert!(lines.len() <= height, "{:?} {}x{}", border, width, height);
+                assert!(
+                    lines.iter().all(|line| line.chars().count() <= width),
+                    "{:?} {}x{}",
+                    border,
+                    width,
+                    height
+                );
+            }
+        }
+        assert_eq!(
+            GridLayout::new(100, 100, 1, Border::Ascii).fit(80, 24),
+            (22, 25)
+        );
+        // too small to fit anything still shows a cell
+        assert_eq!(GridLayout::new(100, 100, 1, Border::Box).fit(0, 0), (1, 1));
+    }
+}
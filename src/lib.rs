@@ -0,0 +1,14 @@
+//! The minesweeper engine: the [Board](board::Board), its [Cell](cell::Cell)s and the [Game](game::Game) rules.
+//! The engine doesn't depend on any graphics library. Front ends are built on top of it with the traits in [frontend],
+//! the ones that ship with the game are in the `minesweeper-app` package.
+
+// the explicit `return` is this crate's style
+#![allow(clippy::needless_return)]
+
+// modules
+pub mod board;
+pub mod cell;
+pub mod frontend;
+pub mod game;
+pub mod label;
+pub mod layout;